@@ -0,0 +1,201 @@
+//! Generates the example tests of every day, see `src/template/example_tests.rs`, and the
+//! in-process solution registry when the `registry` feature is enabled.
+//! See `src/template/registry.rs` for how the generated registry is used.
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // solution binaries are named like `2023-01.rs`.
+    let mut bins: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let bin = name.strip_suffix(".rs")?;
+            let (year, day) = bin.split_once('-')?;
+            let is_number =
+                |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+            (is_number(year, 4) && is_number(day, 2)).then(|| bin.to_string())
+        })
+        .collect();
+
+    bins.sort();
+
+    write_example_tests(Path::new(&manifest_dir), Path::new(&out_dir), &bins);
+
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_some() {
+        write_registry(&bin_dir, Path::new(&out_dir), &bins);
+    }
+}
+
+fn write_registry(bin_dir: &Path, out_dir: &Path, bins: &[String]) {
+    let module_name = |bin: &str| format!("solution_{}", bin.replace('-', "_"));
+
+    let mut out = String::from("// @generated by build.rs, do not edit.\n\n");
+
+    for bin in bins {
+        let path = bin_dir.join(format!("{bin}.rs"));
+        writeln!(out, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(out, "#[allow(dead_code, unused)]").unwrap();
+        writeln!(out, "mod {};", module_name(bin)).unwrap();
+    }
+
+    writeln!(
+        out,
+        "\npub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &["
+    )
+    .unwrap();
+
+    for bin in bins {
+        writeln!(out, "    {}::SOLUTION,", module_name(bin)).unwrap();
+    }
+
+    writeln!(out, "];").unwrap();
+
+    fs::write(out_dir.join("registry.rs"), out).unwrap();
+}
+
+/// Writes `example_tests/{bin}.rs` for every day, which the `example_tests!` macro includes.
+/// It has a test for every key of every sidecar `.expected` file of the examples of the day.
+fn write_example_tests(manifest_dir: &Path, out_dir: &Path, bins: &[String]) {
+    let data_dir = data_dir(manifest_dir);
+    let tests_dir = out_dir.join("example_tests");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    // the runner binary includes the days when the registry is enabled, it has no examples of its own.
+    fs::write(tests_dir.join("advent_of_code.rs"), "").unwrap();
+
+    for bin in bins {
+        let (year, day) = bin.split_once('-').unwrap();
+        let examples_dir = manifest_dir.join(&data_dir).join(year).join("examples");
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+        let mut out = String::from("// @generated by build.rs, do not edit.\n");
+
+        for (stem, keys) in examples(&examples_dir, day) {
+            let path = format!("{data_dir}/{year}/examples/{stem}.txt");
+            // `08.txt` gives `example_part_one`, `08-2.txt` gives `example_2_part_one`.
+            let prefix = match stem.split_once('-') {
+                Some((_, n)) => format!("example_{n}"),
+                None => "example".to_string(),
+            };
+
+            for key in keys {
+                writeln!(out, "\n#[test]").unwrap();
+                writeln!(out, "fn {prefix}_{key}() {{").unwrap();
+                writeln!(
+                    out,
+                    "    advent_of_code::template::example_tests::check({key}, {path:?}, {key:?});"
+                )
+                .unwrap();
+                writeln!(out, "}}").unwrap();
+            }
+        }
+
+        fs::write(tests_dir.join(format!("{bin}.rs")), out).unwrap();
+    }
+}
+
+/// The data directory, from `AOC_DATA_DIR` or `paths.data` of the config file, like `template::config`.
+fn data_dir(manifest_dir: &Path) -> String {
+    println!("cargo:rerun-if-env-changed=AOC_DATA_DIR");
+    println!("cargo:rerun-if-env-changed=AOC_CONFIG");
+
+    if let Ok(dir) = env::var("AOC_DATA_DIR") {
+        return dir;
+    }
+
+    let config = env::var("AOC_CONFIG").unwrap_or_else(|_| "aoc.toml".into());
+    let config = manifest_dir.join(config);
+    println!("cargo:rerun-if-changed={}", config.display());
+
+    // the build script has no TOML parser, `data` is a plain string in `[paths]`.
+    let mut section = String::new();
+    for line in fs::read_to_string(config).unwrap_or_default().lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line.to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            if section == "[paths]" && key.trim() == "data" {
+                let value = value.split('#').next().unwrap_or_default().trim();
+                return value.trim_matches('"').to_string();
+            }
+        }
+    }
+
+    "data".to_string()
+}
+
+/// The non-empty examples of a day that have a sidecar, as `(stem, keys)`, e.g. `("08-2", ["part_two"])`.
+fn examples(examples_dir: &Path, day: &str) -> Vec<(String, Vec<String>)> {
+    let Ok(entries) = fs::read_dir(examples_dir) else {
+        return Vec::new();
+    };
+
+    let mut examples: Vec<(String, Vec<String>)> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".expected")?;
+            let is_example = stem == day
+                || stem
+                    .strip_prefix(day)
+                    .and_then(|s| s.strip_prefix('-'))
+                    .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+            if !is_example {
+                return None;
+            }
+
+            // the placeholders that `scaffold` creates are empty, they get no tests until filled in.
+            let example = fs::read_to_string(examples_dir.join(format!("{stem}.txt"))).ok()?;
+            if example.trim().is_empty() {
+                return None;
+            }
+
+            let sidecar = fs::read_to_string(examples_dir.join(&name)).ok()?;
+            Some((stem.to_string(), keys(&sidecar, &name)))
+        })
+        .collect();
+
+    examples.sort();
+    examples
+}
+
+/// The top-level keys of a sidecar. Values are only checked when the tests run.
+fn keys(sidecar: &str, name: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut in_multiline = false;
+
+    for line in sidecar.lines() {
+        let line = line.trim();
+        if in_multiline {
+            in_multiline = line.matches("\"\"\"").count() % 2 == 0;
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        in_multiline = value.matches("\"\"\"").count() % 2 == 1;
+
+        let key = key.trim();
+        let is_ident = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_ident {
+            keys.push(key.to_string());
+        } else {
+            println!("cargo:warning={name}: \"{key}\" is not a valid test name, skipping it");
+        }
+    }
+
+    keys
+}
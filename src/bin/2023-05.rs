@@ -1,3 +1,5 @@
+use advent_of_code::template::parse::{self, ParseError};
+use advent_of_code::template::solution::Solution;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, newline, space0},
@@ -8,7 +10,7 @@ use nom::{
 };
 use rangemap::RangeMap;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5, Almanac);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct MapEntry {
@@ -154,63 +156,56 @@ fn parse_input(input: &str) -> IResult<&str, Input> {
     )(input)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    println!("input is length {}", input.len());
-    let (_, parsed) = parse_input(input).unwrap();
-
-    let locations = parsed.seeds.iter().map(|seed| {
-        let soil = parsed.seed_to_soil.translate(*seed);
-        let fertilizer = parsed.soil_to_fertilizer.translate(soil);
-        let water = parsed.fertilizer_to_water.translate(fertilizer);
-        let light = parsed.water_to_light.translate(water);
-        let temp = parsed.light_to_temperature.translate(light);
-        let humid = parsed.temperature_to_humidity.translate(temp);
-        let location = parsed.humidity_to_location.translate(humid);
-        location
-    });
-
-    locations.min()
-}
-
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, parsed) = parse_input(input).unwrap();
-
-    let mut result: u64 = u64::MAX;
+struct Almanac;
 
-    for i in (0..parsed.seeds.len()).step_by(2) {
-        let start = *parsed.seeds.get(i).unwrap();
-        let length = *parsed.seeds.get(i + 1).unwrap();
+impl Solution for Almanac {
+    type Parsed<'a> = Input;
+    type Error = ParseError;
+    type PartOne = Option<u64>;
+    type PartTwo = Option<u64>;
 
-        for i in 0..length {
-            let seed = start + i;
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse::parse(input, parse_input)
+    }
 
-            let soil = parsed.seed_to_soil.translate(seed);
+    fn part_one(parsed: &Input) -> Option<u64> {
+        let locations = parsed.seeds.iter().map(|seed| {
+            let soil = parsed.seed_to_soil.translate(*seed);
             let fertilizer = parsed.soil_to_fertilizer.translate(soil);
             let water = parsed.fertilizer_to_water.translate(fertilizer);
             let light = parsed.water_to_light.translate(water);
             let temp = parsed.light_to_temperature.translate(light);
             let humid = parsed.temperature_to_humidity.translate(temp);
             let location = parsed.humidity_to_location.translate(humid);
-            result = result.min(location);
-        }
+            location
+        });
+
+        locations.min()
     }
 
-    Some(result)
-}
+    fn part_two(parsed: &Input) -> Option<u64> {
+        let mut result: u64 = u64::MAX;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        for i in (0..parsed.seeds.len()).step_by(2) {
+            let start = *parsed.seeds.get(i).unwrap();
+            let length = *parsed.seeds.get(i + 1).unwrap();
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
-    }
+            for i in 0..length {
+                let seed = start + i;
+
+                let soil = parsed.seed_to_soil.translate(seed);
+                let fertilizer = parsed.soil_to_fertilizer.translate(soil);
+                let water = parsed.fertilizer_to_water.translate(fertilizer);
+                let light = parsed.water_to_light.translate(water);
+                let temp = parsed.light_to_temperature.translate(light);
+                let humid = parsed.temperature_to_humidity.translate(temp);
+                let location = parsed.humidity_to_location.translate(humid);
+                result = result.min(location);
+            }
+        }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        Some(result)
     }
 }
+
+advent_of_code::example_tests!(solution = Almanac);
@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 #[derive(Debug)]
 struct Input {
@@ -90,19 +90,4 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(ways_to_win(&input))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
-    }
-}
+advent_of_code::example_tests!();
@@ -10,7 +10,7 @@ use nom::{
 };
 use num_integer::Integer;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 #[derive(Debug, Clone, Copy)]
 enum Direction {
@@ -123,23 +123,4 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(steps.into_iter().fold(1, |acc, x| acc.lcm(&x)))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(6));
-    }
-}
+advent_of_code::example_tests!();
@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 #[derive(Debug)]
 struct Input {
@@ -85,19 +85,4 @@ pub fn part_two(input: &str) -> Option<i32> {
     Some(res.iter().sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-}
+advent_of_code::example_tests!();
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(10);
+use advent_of_code::template::parse::ParseError;
+
+advent_of_code::solution!(2023, 10);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Heading {
@@ -43,8 +45,8 @@ struct Input {
     height: i32,
 }
 
-fn parse_pipe(input: char) -> Pipe {
-    match input {
+fn parse_pipe(input: char) -> Option<Pipe> {
+    let pipe = match input {
         '|' => Pipe {
             from: Heading::North,
             to: Heading::South,
@@ -69,8 +71,9 @@ fn parse_pipe(input: char) -> Pipe {
             from: Heading::South,
             to: Heading::East,
         },
-        _ => panic!(),
-    }
+        _ => return None,
+    };
+    Some(pipe)
 }
 
 fn valid_next(
@@ -142,7 +145,7 @@ fn loop_path(start: (i32, i32), map: &HashMap<(i32, i32), Pipe>) -> HashMap<(i32
     result
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
     let mut start: Option<(i32, i32)> = None;
     let mut y = 0;
     let mut x = 0;
@@ -151,13 +154,15 @@ fn parse_input(input: &str) -> Input {
 
     for line in input.lines() {
         x = 0;
-        for char in line.chars() {
+        for (i, char) in line.char_indices() {
             match char {
                 'S' => start = Some((x, y)),
                 '.' => (),
                 _ => {
-                    map.insert((x, y), parse_pipe(char));
-                    ()
+                    let pipe = parse_pipe(char).ok_or_else(|| {
+                        ParseError::at(input, &line[i..], format!("unknown tile '{char}'"))
+                    })?;
+                    map.insert((x, y), pipe);
                 }
             }
 
@@ -167,12 +172,14 @@ fn parse_input(input: &str) -> Input {
         y += 1;
     }
 
-    Input {
-        start: start.unwrap(),
+    let start = start.ok_or_else(|| ParseError::at(input, "", "no start tile 'S' found"))?;
+
+    Ok(Input {
+        start,
         tiles: map,
         height: y,
         width: x,
-    }
+    })
 }
 
 fn adjacent_pipes(start: (i32, i32), map: &HashMap<(i32, i32), Pipe>) -> Vec<Heading> {
@@ -200,11 +207,11 @@ fn place_start(start: (i32, i32), map: &HashMap<(i32, i32), Pipe>) -> HashMap<(i
     map
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let input = parse_input(input);
+pub fn part_one(input: &str) -> Result<Option<i32>, ParseError> {
+    let input = parse_input(input)?;
 
     let map = place_start(input.start, &input.tiles);
-    furthest(input.start, &map)
+    Ok(furthest(input.start, &map))
 }
 
 fn double_tuple(tup: &(i32, i32)) -> (i32, i32) {
@@ -232,8 +239,8 @@ fn clamp(pos: &(i32, i32), width: i32, height: i32) -> Option<(i32, i32)> {
     }
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let input = parse_input(input);
+pub fn part_two(input: &str) -> Result<Option<i32>, ParseError> {
+    let input = parse_input(input)?;
 
     let map = place_start(input.start, &input.tiles);
 
@@ -285,26 +292,7 @@ pub fn part_two(input: &str) -> Option<i32> {
         }
     }
 
-    Some(counter)
+    Ok(Some(counter))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(8));
-    }
-}
+advent_of_code::example_tests!();
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2023, 11);
 
 #[derive(Debug)]
 struct Input {
@@ -80,19 +80,7 @@ pub fn part_two(input: &str) -> Option<i64> {
     Some(solve(input, 1000000))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(374));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(82000210));
-    }
-}
+advent_of_code::example_tests!(
+    expansion_10 => |input| Some(solve(input, 10)),
+    expansion_100 => |input| Some(solve(input, 100)),
+);
@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2023, 13);
 
 struct Pattern {
     rows: Vec<Vec<bool>>,
@@ -115,19 +115,4 @@ pub fn part_two(input: &str) -> Option<u32> {
     solve(input, 1)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(405));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(400));
-    }
-}
+advent_of_code::example_tests!();
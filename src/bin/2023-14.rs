@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2023, 14);
 
 #[derive(Debug)]
 struct Input {
@@ -164,19 +164,4 @@ pub fn part_two(input: &str) -> Option<u64> {
     }).sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(136));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(64));
-    }
-}
+advent_of_code::example_tests!();
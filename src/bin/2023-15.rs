@@ -9,7 +9,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2023, 15);
 
 enum Op {
     Remove(String),
@@ -94,6 +94,8 @@ pub fn part_two(input: &str) -> Option<i64> {
     Some(res)
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,16 +105,4 @@ mod tests {
         let result = hash(&"HASH".to_string());
         assert_eq!(result, 52);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1320));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(145));
-    }
 }
@@ -1,10 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::template::parse::ParseError;
 use itertools::Itertools;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2023, 16);
 
-fn parse_input(input: &str) -> (HashMap<(i64, i64), char>, i64, i64) {
+fn parse_input(input: &str) -> Result<(HashMap<(i64, i64), char>, i64, i64), ParseError> {
     let mut res: HashMap<(i64, i64), char> = HashMap::new();
 
     let mut row = 0;
@@ -18,7 +19,10 @@ fn parse_input(input: &str) -> (HashMap<(i64, i64), char>, i64, i64) {
         }
 
         col = 0;
-        for c in line.chars() {
+        for (i, c) in line.char_indices() {
+            if !matches!(c, '.' | '/' | '\\' | '-' | '|') {
+                return Err(ParseError::at(input, &line[i..], format!("unknown tile '{c}'")));
+            }
             res.insert((row, col), c);
             col += 1;
         }
@@ -26,7 +30,7 @@ fn parse_input(input: &str) -> (HashMap<(i64, i64), char>, i64, i64) {
         row += 1;
     }
 
-    (res, row, col)
+    Ok((res, row, col))
 }
 
 #[derive(PartialEq, Copy, Clone, Debug, Hash, Eq)]
@@ -121,7 +125,7 @@ impl Beam {
                 self.pos = self.direction.offset(self.pos);
                 StepOutcome::Moved
             }
-            Some(_) => panic!(),
+            Some(_) => unreachable!("tiles are validated when parsing"),
             None => StepOutcome::OutOfBounds,
         }
     }
@@ -171,14 +175,14 @@ fn solve(grid: &HashMap<(i64, i64), char>, initial_beam: Beam) -> i64 {
     energised.len() as i64
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let (grid, _rows, _cols) = parse_input(input);
+pub fn part_one(input: &str) -> Result<Option<i64>, ParseError> {
+    let (grid, _rows, _cols) = parse_input(input)?;
 
-    Some(solve(&grid, Beam { pos: (0, 0), direction: Direction::Right }))
+    Ok(Some(solve(&grid, Beam { pos: (0, 0), direction: Direction::Right })))
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let (grid, rows, cols) = parse_input(input);
+pub fn part_two(input: &str) -> Result<Option<i64>, ParseError> {
+    let (grid, rows, cols) = parse_input(input)?;
     let mut res = 0;
 
     res = res.max(solve(&grid, Beam { pos: (0, 0), direction: Direction::Down }));
@@ -201,22 +205,7 @@ pub fn part_two(input: &str) -> Option<i64> {
         res = res.max(solve(&grid, Beam { pos: (y, cols - 1), direction: Direction::Left }));
     }
 
-    Some(res)
+    Ok(Some(res))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(51));
-    }
-}
+advent_of_code::example_tests!();
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use pathfinding::directed::dijkstra::dijkstra;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(2023, 17);
 #[derive(PartialEq, Copy, Clone, Debug, Hash, Eq)]
 enum Direction {
     Up,
@@ -126,25 +126,4 @@ pub fn part_two(input: &str) -> Option<u32> {
     }).map(|(_, c)| c as u32)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(102));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(94));
-    }
-
-    #[test]
-    fn test_example_2() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, Some(71));
-    }
-}
+advent_of_code::example_tests!();
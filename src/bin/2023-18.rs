@@ -0,0 +1,142 @@
+use advent_of_code::template::parse::ParseError;
+use itertools::Itertools;
+
+advent_of_code::solution!(2023, 18);
+
+#[derive(PartialEq, Copy, Clone, Debug, Hash, Eq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn parse_direction(input: &str, direction: &str) -> Result<Direction, ParseError> {
+    match direction {
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "R" => Ok(Direction::Right),
+        _ => Err(ParseError::at(input, direction, "expected a direction (U, D, L or R)")),
+    }
+}
+
+/// The whitespace-separated fields of a line, or an error at the end of the line if there are less than `count`.
+fn fields<'a>(input: &str, line: &'a str, count: usize) -> Result<Vec<&'a str>, ParseError> {
+    let parts = line.split_ascii_whitespace().collect_vec();
+    if parts.len() < count {
+        return Err(ParseError::at(input, &line[line.len()..], format!("expected {count} fields")));
+    }
+    Ok(parts)
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Direction, u64)>, ParseError> {
+    let mut res: Vec<(Direction, u64)> = Vec::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parts = fields(input, line, 2)?;
+        res.push((
+            parse_direction(input, parts[0])?,
+            parts[1].parse().map_err(|_| ParseError::at(input, parts[1], "expected a distance"))?,
+        ));
+    }
+
+    Ok(res)
+}
+
+fn parse_input2(input: &str) -> Result<Vec<(Direction, u64)>, ParseError> {
+    let mut res: Vec<(Direction, u64)> = Vec::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parts = fields(input, line, 3)?;
+        let color = parts[2];
+        let invalid = || ParseError::at(input, color, "expected a color like (#70c710)");
+
+        let hex = color.strip_prefix("(#").and_then(|hex| hex.strip_suffix(')')).ok_or_else(invalid)?;
+        if hex.len() != 6 {
+            return Err(invalid());
+        }
+
+        let distance: u64 = u64::from_str_radix(&hex[..5], 16).map_err(|_| invalid())?;
+
+        let direction = match hex.chars().last() {
+            Some('0') => Direction::Right,
+            Some('1') => Direction::Down,
+            Some('2') => Direction::Left,
+            Some('3') => Direction::Up,
+            _ => return Err(invalid()),
+        };
+        
+        res.push((direction, distance));
+    }
+
+    Ok(res)
+}
+
+fn vertices(instructions: &Vec<(Direction, u64)>) -> Vec<(i64, i64)> {
+    let mut res = Vec::new();
+    
+    let mut current_pos: (i64, i64) = (0, 0);
+    
+    for (direction, distance) in instructions.iter() {
+        let distance = *distance as i64;
+        current_pos = match direction {
+            Direction::Up => (current_pos.0 - distance, current_pos.1),
+            Direction::Down => (current_pos.0 + distance, current_pos.1),
+            Direction::Left => (current_pos.0, current_pos.1 - distance),
+            Direction::Right => (current_pos.0, current_pos.1 + distance),
+        };
+        res.push(current_pos);
+    }
+    res
+}
+
+fn solve(instructions: &Vec<(Direction, u64)>) -> i64 {
+    let vertices = vertices(&instructions);
+    let vertex_count = vertices.len();
+
+    let n: i64 = vertices.iter().enumerate().map(|(i, (row, col))| {
+        let next_index = if i == vertex_count - 1 { 0 } else { i + 1 };
+        let next = vertices.get(next_index).unwrap();
+
+        (*col * next.0) - (next.1 * row) 
+    }).sum();
+
+    let perimeter: i64 = instructions.iter().map(|(_, d)| *d as i64).sum();
+
+
+    (n.abs() / 2) + (perimeter / 2 + 1)
+}
+
+pub fn part_one(input: &str) -> Result<Option<i64>, ParseError> {
+    let input = parse_input(input)?;
+    Ok(Some(solve(&input)))
+}
+
+pub fn part_two(input: &str) -> Result<Option<i64>, ParseError> {
+    let input = parse_input2(input)?;
+    Ok(Some(solve(&input)))
+}
+
+advent_of_code::example_tests!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_input() {
+        let err = part_one("R 6 (#70c710)\nX 5 (#0dc571)\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 1));
+    }
+}
@@ -10,7 +10,7 @@ use nom::{
     sequence::{preceded, terminated, tuple},
     IResult,
 };
-advent_of_code::solution!(19);
+advent_of_code::solution!(2023, 19);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Operator {
@@ -237,13 +237,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(19114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
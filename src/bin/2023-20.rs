@@ -13,7 +13,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2023, 20);
 
 #[derive(Debug)]
 enum Module {
@@ -181,26 +181,15 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one_example_1() {
-        let result: Option<u64> = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1));
-        assert_eq!(result, Some(32000000));
-    }
-
-
-    #[test]
-    fn test_part_one_example_2() {
-        let result: Option<u64> = part_one(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, Some(11687500));
-    }
-
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file_part("examples", YEAR, DAY, 1));
         assert_eq!(result, None);
     }
 }
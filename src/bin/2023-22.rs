@@ -10,7 +10,7 @@ use nom::{
 };
 use rangemap::RangeInclusiveMap;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2023, 22);
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 struct Point3D {
@@ -234,19 +234,15 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
-    }
-
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use advent_of_code::solution;
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(2023, 23);
 
 #[derive(PartialEq)]
 enum Direction {
@@ -213,19 +213,4 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(longest_walk)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(94));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(154));
-    }
-}
+advent_of_code::example_tests!();
@@ -4,7 +4,7 @@ use itertools::Itertools;
 use priority_queue::PriorityQueue;
 
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2023, 25);
 
 #[derive(Clone)]
 struct Graph<'a> {
@@ -169,19 +169,15 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(54));
-    }
-
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
@@ -1,4 +1,10 @@
 mod day;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use year::*;
+
+#[cfg(feature = "alloc_profile")]
+#[global_allocator]
+static GLOBAL: template::alloc::CountingAllocator = template::alloc::CountingAllocator;
@@ -1,55 +1,212 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench_compare, download, examples, read, scaffold, solve, stars, status, verify, watch,
+};
+use advent_of_code::template::input::Source;
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::commands::{bench_compare, scaffold};
+    use advent_of_code::template::config;
+    use advent_of_code::template::input::Source;
+    use advent_of_code::template::limits::Limits;
+    use advent_of_code::template::report::Format;
+    use advent_of_code::{Day, Year};
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
             day: Day,
+            part: Option<u8>,
+        },
+        Examples {
+            year: Year,
+            day: Day,
+            block_one: Option<usize>,
+            block_two: Option<usize>,
         },
         Scaffold {
+            year: Year,
             day: Day,
+            options: scaffold::Options,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            part: Option<u8>,
+            watch: bool,
+            examples: bool,
+            input: Source,
         },
         All {
+            year: Year,
             release: bool,
             time: bool,
+            part: Option<u8>,
+            limits: Limits,
+            format: Format,
+            jobs: usize,
+        },
+        Verify {
+            year: Year,
+            release: bool,
+        },
+        Stars {
+            year: Year,
+        },
+        Status {
+            year: Year,
+            markdown: bool,
+            run_tests: bool,
+        },
+        BenchCompare {
+            year: Year,
+            baseline: Option<String>,
+            save: Option<String>,
+            threshold: f64,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // a malformed config file is an error here, instead of falling back to the defaults.
+        let config = config::init().map_err(|e| e.to_string())?;
+
+        let year = match args.opt_value_from_str("--year")? {
+            Some(year) => year,
+            None => Year::from_env().or(config.year).ok_or(
+                "no year specified, pass `--year <year>`, set the AOC_YEAR environment variable or `year` in aoc.toml",
+            )?,
+        };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                part: args.opt_value_from_fn("--part", parse_part)?,
+                limits: Limits {
+                    day_timeout: args
+                        .opt_value_from_fn("--timeout", parse_seconds)?
+                        .or(config.all.timeout.map(Duration::from_secs_f64)),
+                    part_timeout: args
+                        .opt_value_from_fn("--part-timeout", parse_seconds)?
+                        .or(config.all.part_timeout.map(Duration::from_secs_f64)),
+                    memory_mb: args
+                        .opt_value_from_str("--memory-limit")?
+                        .or(config.all.memory_limit),
+                },
+                format: match args.opt_value_from_str("--format")? {
+                    Some(format) => format,
+                    None => config
+                        .all
+                        .format
+                        .as_deref()
+                        .map(str::parse::<Format>)
+                        .transpose()?
+                        .unwrap_or_default(),
+                },
+                jobs: args
+                    .opt_value_from_str(["-j", "--jobs"])?
+                    .or(config.all.jobs)
+                    .unwrap_or(1),
             },
             Some("download") => AppArguments::Download {
+                year,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year,
+                part: args.opt_value_from_fn("--part", parse_part)?,
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("examples") => AppArguments::Examples {
+                year,
                 day: args.free_from_str()?,
+                block_one: args.opt_value_from_str("--block")?,
+                block_two: args.opt_value_from_str("--block-two")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("scaffold") => {
+                let defaults = scaffold::Options::default();
+                AppArguments::Scaffold {
+                    year,
+                    day: args.free_from_str()?,
+                    options: scaffold::Options {
+                        template: args
+                            .opt_value_from_str("--template")?
+                            .or_else(|| config.scaffold.template.clone())
+                            .unwrap_or(defaults.template),
+                        answer_type: args
+                            .opt_value_from_str("--answer")?
+                            .or_else(|| config.scaffold.answer.clone())
+                            .unwrap_or(defaults.answer_type),
+                        part_examples: args.contains("--part-examples"),
+                    },
+                }
+            }
+            Some("solve") => {
+                // flags go first, so the optional part of `--example [n]` is the only free argument left after the day.
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let part = args.opt_value_from_fn("--part", parse_part)?;
+                let watch = args.contains("--watch");
+                let examples = args.contains("--examples");
+                let example = args.contains("--example");
+                let input_path: Option<String> = args.opt_value_from_str("--input")?;
+                let day = args.free_from_str()?;
+
+                let input = match (example, input_path) {
+                    (true, Some(_)) => {
+                        return Err("`--example` and `--input` cannot be combined".into())
+                    }
+                    (true, None) => Source::Example(args.opt_free_from_str()?),
+                    (false, Some(path)) => Source::from_path(&path),
+                    (false, None) => Source::Puzzle,
+                };
+
+                AppArguments::Solve {
+                    year,
+                    day,
+                    release,
+                    time,
+                    submit,
+                    part,
+                    watch,
+                    examples,
+                    input,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                year,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            },
+            Some("stars") => AppArguments::Stars { year },
+            Some("status") => AppArguments::Status {
+                year,
+                markdown: args.contains("--markdown"),
+                run_tests: !args.contains("--no-tests"),
+            },
+            Some("bench-compare") => AppArguments::BenchCompare {
+                year,
+                baseline: args.opt_value_from_str("--baseline")?,
+                save: args.opt_value_from_str("--save")?,
+                threshold: args
+                    .opt_value_from_str("--threshold")?
+                    .or(config.bench_threshold)
+                    .unwrap_or(bench_compare::DEFAULT_THRESHOLD),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,25 +225,111 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_seconds(s: &str) -> Result<Duration, String> {
+        match s.parse::<f64>() {
+            Ok(secs) if secs > 0.0 && secs.is_finite() => Ok(Duration::from_secs_f64(secs)),
+            _ => Err(format!(
+                "invalid timeout \"{s}\", expecting a number of seconds"
+            )),
+        }
+    }
+
+    fn parse_part(s: &str) -> Result<u8, String> {
+        match s {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            x => Err(format!("unknown part \"{x}\", expecting 1 or 2")),
+        }
+    }
+}
+
+#[cfg(feature = "registry")]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
 }
 
 fn main() {
+    #[cfg(feature = "registry")]
+    {
+        advent_of_code::template::registry::register(registry::SOLUTIONS);
+        // linked solutions run in this process, their panics are reported like those of solution binaries.
+        advent_of_code::template::runner::install_panic_hook();
+    }
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                time,
+                part,
+                limits,
+                format,
+                jobs,
+            } => all::handle(year, release, time, part, &limits, format, jobs),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day, part } => read::handle(year, day, part),
+            AppArguments::Examples {
+                year,
+                day,
+                block_one,
+                block_two,
+            } => examples::handle(year, day, block_one, block_two),
+            AppArguments::Scaffold { year, day, options } => {
+                scaffold::handle(year, day, &options);
+            }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                part,
+                watch,
+                examples,
+                input,
+            } => {
+                if watch {
+                    if time || submit.is_some() || input == Source::Stdin {
+                        eprintln!(
+                            "`--watch` cannot be combined with `--time`, `--submit` or `--input -`."
+                        );
+                        std::process::exit(1);
+                    }
+                    watch::handle(year, day, release, examples, &input);
+                } else if examples {
+                    eprintln!("`--examples` requires `--watch`.");
+                    std::process::exit(1);
+                } else {
+                    if let (Some(part), Some(submit)) = (part, submit) {
+                        if part != submit {
+                            eprintln!(
+                                "Cannot submit part {submit} while only running part {part}."
+                            );
+                            std::process::exit(1);
+                        }
+                    }
+                    solve::handle(year, day, release, time, submit, part, &input);
+                }
+            }
+            AppArguments::Verify { year, release } => verify::handle(year, release),
+            AppArguments::Stars { year } => stars::handle(year),
+            AppArguments::Status {
+                year,
+                markdown,
+                run_tests,
+            } => status::handle(year, markdown, run_tests),
+            AppArguments::BenchCompare {
+                year,
+                baseline,
+                save,
+                threshold,
+            } => bench_compare::handle(year, baseline, save, threshold),
         },
     };
 }
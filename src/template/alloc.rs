@@ -0,0 +1,198 @@
+/// Memory profiling of solution parts.
+///
+/// [`CountingAllocator`] wraps the system allocator and counts allocations, allocated bytes and the
+/// live heap. With the `alloc_profile` feature, the library installs it as the global allocator, so
+/// every solution binary reports these figures per part. Without the feature, the counters stay at
+/// zero and only the peak resident set size of the process is reported.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts what passes through it.
+/// Counters are process-wide, so allocations of threads spawned by a part (e.g. by rayon) are included.
+/// For the same reason, days that `all --jobs` solves concurrently in one process count each other's
+/// allocations, see the `registry` feature.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as a new allocation of the new size that frees the old one.
+            LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+/// Allocations made while a part ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Allocations {
+    /// Number of allocations, including reallocations.
+    pub count: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Peak size of the heap the part allocated on top of what was live before it started.
+    pub peak_bytes: u64,
+}
+
+/// Memory figures of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Memory {
+    /// Only known if the [`CountingAllocator`] is installed.
+    pub allocations: Option<Allocations>,
+    /// Peak resident set size of the whole process once the part finished, in bytes.
+    pub peak_rss: Option<u64>,
+}
+
+/// Runs `func`, counting the allocations it makes.
+/// Returns `None` for the allocations if the [`CountingAllocator`] is not installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Memory) {
+    let live_before = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live_before, Ordering::Relaxed);
+    let count_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes_before = ALLOCATED_BYTES.load(Ordering::Relaxed);
+
+    let result = func();
+
+    let count = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocations = is_counting().then(|| Allocations {
+        count: count - count_before,
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes_before,
+        peak_bytes: PEAK_LIVE_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(live_before),
+    });
+
+    let memory = Memory {
+        allocations,
+        peak_rss: peak_rss(),
+    };
+
+    (result, memory)
+}
+
+/// Whether the [`CountingAllocator`] is installed. Any Rust program allocates before `main`,
+/// so the counters are never zero once it is.
+#[must_use]
+pub fn is_counting() -> bool {
+    ALLOCATIONS.load(Ordering::Relaxed) > 0
+}
+
+/// Peak resident set size of this process in bytes, as reported by `getrusage`.
+#[cfg(unix)]
+#[must_use]
+pub fn peak_rss() -> Option<u64> {
+    // SAFETY: `rusage` is plain data, `getrusage` only writes to the passed struct.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+        return None;
+    }
+
+    let max_rss = u64::try_from(usage.ru_maxrss).ok()?;
+
+    // macOS reports bytes, other unix systems kilobytes.
+    if cfg!(target_os = "macos") {
+        Some(max_rss)
+    } else {
+        Some(max_rss * 1024)
+    }
+}
+
+#[cfg(not(unix))]
+#[must_use]
+pub fn peak_rss() -> Option<u64> {
+    None
+}
+
+/// Formats a number of bytes with a binary unit, e.g. like `1.5 MiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn reports_peak_rss() {
+        let (result, memory) = measure(|| 42);
+        assert_eq!(result, 42);
+        if cfg!(unix) {
+            assert!(memory.peak_rss.unwrap() > 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc_profile")]
+    fn counts_allocations() {
+        use std::hint::black_box;
+
+        let (_, memory) = measure(|| black_box(Vec::<u64>::with_capacity(1000)));
+        let allocations = memory.allocations.unwrap();
+
+        // other tests allocate concurrently, so the counters are a lower bound.
+        assert!(allocations.count >= 1);
+        assert!(allocations.bytes >= 8000);
+    }
+}
@@ -0,0 +1,142 @@
+/// Store of known-correct answers for the real puzzle inputs.
+///
+/// Answers live in `data/{year}/answers/{day}.toml`:
+///
+/// ```toml
+/// part_one = 35
+/// part_two = "46"
+/// ```
+use std::fmt::Display;
+use std::{fs, io};
+
+use toml::{Table, Value};
+
+use crate::template::get_data_path;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed answer file: {e}"),
+            Error::IO(e) => write!(f, "could not read answer file: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// Returns the recorded answer for a part, if any.
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether `answer` matches the recorded answer for `part`.
+    /// Returns [`None`] if no answer has been recorded.
+    #[must_use]
+    pub fn matches(&self, part: u8, answer: &str) -> Option<bool> {
+        self.get(part)
+            .map(|expected| expected.trim() == answer.trim())
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year, day: Day) -> String {
+    get_data_path("answers", year, &format!("{day}.toml"))
+}
+
+/// Reads the answers recorded for a day. A missing answer file yields empty [`Answers`].
+pub fn read(year: Year, day: Day) -> Result<Answers, Error> {
+    match fs::read_to_string(get_path(year, day)) {
+        Ok(s) => parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse(s: &str) -> Result<Answers, Error> {
+    let table: Table = s.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+
+    if let Some(key) = table
+        .keys()
+        .find(|key| *key != "part_one" && *key != "part_two")
+    {
+        return Err(Error::Parser(format!("unknown key \"{key}\"")));
+    }
+
+    Ok(Answers {
+        part_one: parse_value(&table, "part_one")?,
+        part_two: parse_value(&table, "part_two")?,
+    })
+}
+
+pub(crate) fn parse_value(table: &Table, key: &str) -> Result<Option<String>, Error> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(Value::Integer(i)) => Ok(Some(i.to_string())),
+        Some(_) => Err(Error::Parser(format!(
+            "\"{key}\" must be a string or an integer"
+        ))),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_integers_and_strings() {
+        let answers = parse("part_one = 35\npart_two = \"46\"\n").unwrap();
+        assert_eq!(answers.get(1), Some("35"));
+        assert_eq!(answers.get(2), Some("46"));
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = parse("part_one = 35\n").unwrap();
+        assert_eq!(answers.get(1), Some("35"));
+        assert_eq!(answers.get(2), None);
+        assert_eq!(answers.matches(2, "46"), None);
+    }
+
+    #[test]
+    fn matches_multiline_answers() {
+        let answers = parse("part_two = \"\"\"\n#..#\n.##.\n\"\"\"\n").unwrap();
+        assert_eq!(answers.matches(2, "#..#\n.##."), Some(true));
+        assert_eq!(answers.matches(2, "#..#\n.#.."), Some(false));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_unknown_keys() {
+        parse("part_three = 1\n").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_invalid_values() {
+        parse("part_one = 1.5\n").unwrap();
+    }
+}
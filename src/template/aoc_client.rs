@@ -0,0 +1,510 @@
+/// Built-in client for the Advent of Code website.
+///
+/// Authenticates with the session cookie of a logged-in browser. The cookie is read from the
+/// `AOC_SESSION` environment variable, or from the file at `AOC_SESSION_FILE` or `paths.session_file` of `aoc.toml`
+/// (default: `~/.adventofcode.session`).
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::template::{config, get_data_path, html};
+use crate::{Day, Year};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+const USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/fspoettel/advent-of-code-rust)"
+);
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Unauthorized,
+    NotFound,
+    Http(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or store it in ~/{SESSION_FILE_NAME}."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            AocClientError::NotFound => {
+                write!(f, "the puzzle does not exist or is not unlocked yet.")
+            }
+            AocClientError::Http(status) => write!(f, "unexpected response status {status}."),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "could not write output files: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            // the site answers with 400 (or 500 for garbled cookies) when a session is not valid.
+            ureq::Error::Status(400 | 401 | 403 | 500, _) => AocClientError::Unauthorized,
+            ureq::Error::Status(404, _) => AocClientError::NotFound,
+            ureq::Error::Status(status, _) => AocClientError::Http(status),
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// The verdict for a submitted answer, as reported by the website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    /// The answer is wrong and greater than the correct answer.
+    TooHigh {
+        wait: Option<Duration>,
+    },
+    /// The answer is wrong and less than the correct answer.
+    TooLow {
+        wait: Option<Duration>,
+    },
+    /// The answer is wrong, without a hint about its direction.
+    Incorrect {
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently; nothing was checked.
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part was already solved, or is not unlocked yet.
+    AlreadySolved,
+    /// The response could not be interpreted. Contains the text of the response.
+    Unknown(String),
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fmt_wait = |wait: &Option<Duration>| match wait {
+            Some(wait) => format!(" Wait {} before trying again.", format_wait(*wait)),
+            None => String::new(),
+        };
+
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::TooHigh { wait } => {
+                write!(
+                    f,
+                    "That's not the right answer, it is too high.{}",
+                    fmt_wait(wait)
+                )
+            }
+            SubmissionOutcome::TooLow { wait } => {
+                write!(
+                    f,
+                    "That's not the right answer, it is too low.{}",
+                    fmt_wait(wait)
+                )
+            }
+            SubmissionOutcome::Incorrect { wait } => {
+                write!(f, "That's not the right answer.{}", fmt_wait(wait))
+            }
+            SubmissionOutcome::RateLimited { wait } => write!(
+                f,
+                "You gave an answer too recently. Wait {} before trying again.",
+                format_wait(*wait)
+            ),
+            SubmissionOutcome::AlreadySolved => {
+                write!(f, "This part is already solved or not unlocked yet.")
+            }
+            SubmissionOutcome::Unknown(text) => write!(f, "Unrecognized response: {text}"),
+        }
+    }
+}
+
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(TIMEOUT)
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    /// Creates a client from the environment. The base URL can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}/input", year, day.into_inner()))
+    }
+
+    /// Fetches the HTML of the puzzle page of a day.
+    pub fn puzzle_html(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}", year, day.into_inner()))
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day.into_inner());
+
+        let body = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        Ok(parse_submission(&body))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let url = format!("{}{}", self.base_url, path);
+        let body = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+        Ok(body)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Reads the session cookie from `AOC_SESSION` or the session file.
+fn read_session() -> Option<String> {
+    let session = match env::var(SESSION_ENV) {
+        Ok(session) => session,
+        Err(_) => fs::read_to_string(session_file_path()?).ok()?,
+    };
+
+    let session = session.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(SESSION_FILE_ENV) {
+        return Some(path.into());
+    }
+    if let Some(path) = &config::get().session_file {
+        return Some(path.clone());
+    }
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(Path::new(&home).join(SESSION_FILE_NAME))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Interprets the response page of an answer submission.
+#[must_use]
+pub fn parse_submission(page: &str) -> SubmissionOutcome {
+    let nodes = html::parse(page);
+    let text = match html::find_all(&nodes, "article").first() {
+        Some(article) => article.text(),
+        None => page.to_string(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let wait = parse_wait(&text);
+
+    if text.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if text.contains("You gave an answer too recently") {
+        SubmissionOutcome::RateLimited {
+            wait: wait.unwrap_or_default(),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::AlreadySolved
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            SubmissionOutcome::TooHigh { wait }
+        } else if text.contains("your answer is too low") {
+            SubmissionOutcome::TooLow { wait }
+        } else {
+            SubmissionOutcome::Incorrect { wait }
+        }
+    } else {
+        SubmissionOutcome::Unknown(text)
+    }
+}
+
+/// Finds the wait time in texts like "You have 1m 20s left to wait" or "please wait 5 minutes before trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("You have ").map(|i| i + 9)?;
+        let mut secs = 0;
+        for token in text[start..end].split_whitespace() {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            secs += match unit {
+                "h" => value * 3600,
+                "m" => value * 60,
+                "s" => value,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+
+    let start = text.find("wait ")? + 5;
+    let mut words = text[start..].split_whitespace();
+    let amount = match words.next()? {
+        "one" | "a" | "an" => 1,
+        n => n.parse().ok()?,
+    };
+    let unit = match words.next()? {
+        w if w.starts_with("second") => 1,
+        w if w.starts_with("minute") => 60,
+        w if w.starts_with("hour") => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(amount * unit))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[must_use]
+pub fn get_input_path(year: Year, day: Day) -> String {
+    get_data_path("inputs", year, &format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn get_puzzle_path(year: Year, day: Day) -> String {
+    get_data_path("puzzles", year, &format!("{day}.md"))
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Downloads the input and the puzzle description of a day to the data directory.
+pub fn download(client: &AocClient, year: Year, day: Day) -> Result<(), AocClientError> {
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    let input = client.input(year, day)?;
+    let puzzle = html::puzzle_to_markdown(&client.puzzle_html(year, day)?);
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetches the current puzzle description of a day, storing it in the data directory.
+pub fn read(client: &AocClient, year: Year, day: Day) -> Result<String, AocClientError> {
+    let puzzle = html::puzzle_to_markdown(&client.puzzle_html(year, day)?);
+    write_file(&get_puzzle_path(year, day), &puzzle)?;
+    Ok(puzzle)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{parse_submission, parse_wait, AocClient, AocClientError, SubmissionOutcome};
+    use crate::{day, year};
+
+    /// Serves the given responses in order from a local port, returning the base URL and
+    /// a channel that receives the raw requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body_buf = vec![0; content_length];
+                reader.read_exact(&mut body_buf).unwrap();
+                request.push_str(&String::from_utf8(body_buf).unwrap());
+                tx.send(request).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn downloads_input_with_session_cookie() {
+        let (base_url, requests) = serve(vec![(200, "1\n2\n3\n")]);
+        let client = AocClient::new(&base_url, "abc123");
+
+        let input = client.input(year!(2023), day!(5)).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/5/input "));
+        assert!(request.contains("session=abc123"));
+    }
+
+    #[test]
+    fn maps_error_statuses() {
+        let (base_url, _requests) = serve(vec![(404, "Not Found"), (400, "Please log in.")]);
+        let client = AocClient::new(&base_url, "abc123");
+
+        let not_found = client.input(year!(2023), day!(25));
+        assert!(matches!(not_found, Err(AocClientError::NotFound)));
+
+        let unauthorized = client.input(year!(2023), day!(25));
+        assert!(matches!(unauthorized, Err(AocClientError::Unauthorized)));
+    }
+
+    #[test]
+    fn submits_answer_as_form() {
+        let page = "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>";
+        let (base_url, requests) = serve(vec![(200, page)]);
+        let client = AocClient::new(&base_url, "abc123");
+
+        let outcome = client.submit(year!(2023), day!(5), 2, "42").unwrap();
+        assert_eq!(outcome, SubmissionOutcome::Correct);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_wrong_answers() {
+        let high = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+        assert_eq!(
+            parse_submission(high),
+            SubmissionOutcome::TooHigh {
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+
+        let low = "<article><p>That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.</p></article>";
+        assert_eq!(
+            parse_submission(low),
+            SubmissionOutcome::TooLow {
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+
+        let wrong = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>";
+        assert_eq!(
+            parse_submission(wrong),
+            SubmissionOutcome::Incorrect { wait: None }
+        );
+    }
+
+    #[test]
+    fn parses_rate_limits_and_solved_parts() {
+        let limited = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 20s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+        assert_eq!(
+            parse_submission(limited),
+            SubmissionOutcome::RateLimited {
+                wait: Duration::from_secs(80)
+            }
+        );
+
+        let solved = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+        assert_eq!(parse_submission(solved), SubmissionOutcome::AlreadySolved);
+
+        assert!(matches!(
+            parse_submission("<article><p>Something else.</p></article>"),
+            SubmissionOutcome::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You have 41s left to wait."),
+            Some(Duration::from_secs(41))
+        );
+        assert_eq!(
+            parse_wait("please wait 10 minutes before trying again"),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(parse_wait("no waiting here"), None);
+    }
+}
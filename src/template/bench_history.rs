@@ -0,0 +1,358 @@
+/// History of benchmark runs, used to spot performance regressions between runs.
+///
+/// Every timed run of `solve` or `all` appends one JSON object per line to `data/{year}/benchmarks.jsonl`,
+/// recording the commit it ran on, when it ran and the statistics of every part.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::get_year_path;
+use crate::template::ledger::now;
+use crate::template::report::PartReport;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed benchmark history: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// One timed run of `solve` or `all`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    /// Abbreviated hash of the commit the run was made on, if it ran inside a git checkout.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub release: bool,
+    /// Name that marks this run as a baseline to compare against.
+    pub name: Option<String>,
+    pub results: Vec<PartReport>,
+}
+
+impl Run {
+    /// Creates a run for the current commit and time.
+    #[must_use]
+    pub fn new(release: bool, results: Vec<PartReport>) -> Self {
+        Self {
+            commit: current_commit(),
+            timestamp: now(),
+            release,
+            name: None,
+            results,
+        }
+    }
+
+    fn find(&self, day: Day, part: u8) -> Option<&PartReport> {
+        self.results
+            .iter()
+            .find(|r| r.day == day && r.part == part && r.answer.is_some())
+    }
+
+    /// Whether the run is referred to by `name`, either as its baseline name or by a prefix of its commit.
+    #[must_use]
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.as_deref() == Some(name)
+            || self
+                .commit
+                .as_deref()
+                .is_some_and(|commit| !name.is_empty() && commit.starts_with(name))
+    }
+
+    /// A short description of the run, e.g. like `baseline "v1" (a1b2c3d)`.
+    #[must_use]
+    pub fn label(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown commit");
+        match &self.name {
+            Some(name) => format!("baseline \"{name}\" ({commit})"),
+            None => commit.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        let mut obj: HashMap<String, JsonValue> = HashMap::new();
+        obj.insert(
+            "commit".into(),
+            self.commit.clone().map_or(JsonValue::Null, JsonValue::from),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        obj.insert("timestamp".into(), (self.timestamp as f64).into());
+        obj.insert("release".into(), self.release.into());
+        obj.insert(
+            "name".into(),
+            self.name.clone().map_or(JsonValue::Null, JsonValue::from),
+        );
+        obj.insert(
+            "results".into(),
+            JsonValue::Array(self.results.iter().map(PartReport::to_json).collect()),
+        );
+        obj.into()
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_json(value: &JsonValue) -> Result<Self, Error> {
+        let obj: &HashMap<String, JsonValue> = value
+            .get()
+            .ok_or_else(|| Error::Parser("expected an object".into()))?;
+
+        let optional_string = |key: &str| match obj.get(key) {
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(JsonValue::Null) | None => Ok(None),
+            Some(_) => Err(Error::Parser(format!("\"{key}\" is not a string"))),
+        };
+
+        let timestamp = obj
+            .get("timestamp")
+            .and_then(JsonValue::get::<f64>)
+            .ok_or_else(|| Error::Parser("missing numeric field \"timestamp\"".into()))?;
+
+        let release = obj
+            .get("release")
+            .and_then(JsonValue::get::<bool>)
+            .ok_or_else(|| Error::Parser("missing field \"release\"".into()))?;
+
+        let results: &Vec<JsonValue> = obj
+            .get("results")
+            .and_then(JsonValue::get)
+            .ok_or_else(|| Error::Parser("missing field \"results\"".into()))?;
+
+        Ok(Self {
+            commit: optional_string("commit")?,
+            timestamp: *timestamp as u64,
+            release: *release,
+            name: optional_string("name")?,
+            results: results
+                .iter()
+                .map(|r| PartReport::from_json(r).map_err(|e| Error::Parser(e.to_string())))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Abbreviated hash of `HEAD`, marked with `-dirty` if the working tree has uncommitted changes.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty", "--exclude=*"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Change of the median execution time of one part between two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+    /// Relative change in percent, positive if the part got slower.
+    pub change: f64,
+}
+
+impl Delta {
+    /// Whether the part got slower by more than `threshold` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change > threshold
+    }
+}
+
+/// Compares the parts of `current` against the same parts in `previous`.
+/// Parts that are missing from either run are skipped.
+#[must_use]
+pub fn compare(previous: &Run, current: &Run) -> Vec<Delta> {
+    let mut deltas: Vec<Delta> = current
+        .results
+        .iter()
+        .filter(|r| r.answer.is_some())
+        .filter_map(|after| {
+            let before = previous.find(after.day, after.part)?;
+            let before_secs = before.stats.median.as_secs_f64();
+            let after_secs = after.stats.median.as_secs_f64();
+
+            let change = if before_secs > 0.0 {
+                (after_secs - before_secs) / before_secs * 100.0
+            } else {
+                0.0
+            };
+
+            Some(Delta {
+                day: after.day,
+                part: after.part,
+                before: before.stats.median,
+                after: after.stats.median,
+                change,
+            })
+        })
+        .collect();
+
+    deltas.sort_by_key(|d| (d.day, d.part));
+    deltas
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Path of the benchmark history of a year. E.g. like `data/2023/benchmarks.jsonl`.
+#[must_use]
+pub fn get_path(year: Year) -> String {
+    get_year_path(year, "benchmarks.jsonl")
+}
+
+/// Reads all runs of a year, oldest first. A missing history is treated as empty.
+pub fn read(year: Year) -> Result<Vec<Run>, Error> {
+    match fs::read_to_string(get_path(year)) {
+        Ok(contents) => parse(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Appends a run to the history of a year.
+pub fn append(year: Year, run: &Run) -> Result<(), Error> {
+    let path = get_path(year);
+
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", stringify(run)?)?;
+    Ok(())
+}
+
+/// Replaces the history of a year, e.g. after naming a run.
+pub fn write(year: Year, runs: &[Run]) -> Result<(), Error> {
+    let mut contents = String::new();
+    for run in runs {
+        contents.push_str(&stringify(run)?);
+        contents.push('\n');
+    }
+    fs::write(get_path(year), contents)?;
+    Ok(())
+}
+
+fn stringify(run: &Run) -> Result<String, Error> {
+    run.to_json()
+        .stringify()
+        .map_err(|e| Error::Parser(e.to_string()))
+}
+
+fn parse(contents: &str) -> Result<Vec<Run>, Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let value: JsonValue = line.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+            Run::from_json(&value)
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, parse, Run};
+    use crate::template::alloc::Memory;
+    use crate::template::report::{PartReport, Status};
+    use crate::template::stats::Stats;
+    use crate::{day, year, Day};
+
+    fn report(day: Day, part: u8, median_micros: u64) -> PartReport {
+        PartReport {
+            year: year!(2023),
+            day,
+            part,
+            status: Status::Solved,
+            answer: Some("42".into()),
+            error: None,
+            stats: Stats::single(Duration::from_micros(median_micros)),
+            memory: Memory::default(),
+        }
+    }
+
+    fn run(commit: &str, results: Vec<PartReport>) -> Run {
+        Run {
+            commit: Some(commit.into()),
+            timestamp: 1_700_000_000,
+            release: true,
+            name: None,
+            results,
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut run = run("a1b2c3d", vec![report(day!(1), 1, 10)]);
+        run.name = Some("v1".into());
+
+        let line = run.to_json().stringify().unwrap();
+        assert_eq!(parse(&format!("{line}\n\n")).unwrap(), vec![run]);
+    }
+
+    #[test]
+    fn computes_deltas() {
+        let previous = run(
+            "a1b2c3d",
+            vec![report(day!(1), 1, 100), report(day!(1), 2, 100)],
+        );
+        let current = run(
+            "e4f5a6b",
+            vec![
+                report(day!(1), 2, 90),
+                report(day!(1), 1, 150),
+                report(day!(2), 1, 10),
+            ],
+        );
+
+        let deltas = compare(&previous, &current);
+        assert_eq!(deltas.len(), 2);
+
+        assert_eq!((deltas[0].day, deltas[0].part), (day!(1), 1));
+        assert!((deltas[0].change - 50.0).abs() < 1e-9);
+        assert!(deltas[0].is_regression(10.0));
+
+        assert!((deltas[1].change + 10.0).abs() < 1e-9);
+        assert!(!deltas[1].is_regression(10.0));
+    }
+
+    #[test]
+    fn matches_names_and_commits() {
+        let mut run = run("a1b2c3d", vec![]);
+        run.name = Some("v1".into());
+
+        assert!(run.is_named("v1"));
+        assert!(run.is_named("a1b2"));
+        assert!(!run.is_named("b1"));
+        assert!(!run.is_named(""));
+    }
+}
@@ -1,46 +1,371 @@
-use std::io;
+use std::collections::HashMap;
+use std::{io, process};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tinyjson::JsonValue;
 
 use crate::template::{
+    alloc, answers,
+    bench_history::{self, Run},
+    config, get_bin_name, get_feature_args, get_path_for_bin,
+    input::Source,
+    limits::Limits,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::{self, Solution},
+    report::{Format, PartReport, Status},
+    runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::{all_days, Day, Year};
+
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    is_timed: bool,
+    part: Option<u8>,
+    limits: &Limits,
+    format: Format,
+    jobs: usize,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let mut reports: Vec<PartReport> = vec![];
+    let mut days: Vec<(Day, DayStatus)> = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+    let echo = match format {
+        Format::Human => Echo::Stdout,
+        // keep stdout clean for the JSON document.
+        Format::Json => Echo::Stderr,
+    };
+
+    let mut collect = |day: Day, day_reports: Vec<PartReport>| {
+        days.push((day, DayStatus::from_reports(&day_reports)));
+
+        if day_reports.is_empty() {
+            if format == Format::Human {
+                println!("Not scaffolded.");
+            }
+        } else {
+            let answers = answers::read(year, day).unwrap_or_default();
+            timings.push(child_commands::collect_timings(&day_reports, day, &answers));
+            reports.extend(day_reports);
         }
+    };
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    if !limits.is_empty() && all_days().any(|day| registry::find(year, day).is_some()) {
+        eprintln!("Note: timeouts and memory limits only apply to solutions that are not linked into this binary.");
+    }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if jobs != 1
+        && !is_timed
+        && alloc::is_counting()
+        && all_days().any(|day| registry::find(year, day).is_some())
+    {
+        eprintln!("Note: days solved concurrently in this binary count each other's allocations.");
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    if jobs != 1 && is_timed {
+        eprintln!("Note: ignoring --jobs, days are benchmarked one at a time to keep timings trustworthy.");
+    }
+
+    if jobs == 1 || is_timed {
+        all_days().for_each(|day| {
+            print_header(day, format);
+            let (day_reports, _) =
+                run_day(year, day, is_timed, is_release, part, limits, echo).unwrap();
+            collect(day, day_reports);
+        });
+    } else {
+        // solve days concurrently, holding back their output so it can be printed in order.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .unwrap();
+
+        let results: Vec<_> = pool.install(|| {
+            all_days()
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|day| {
+                    (
+                        day,
+                        run_day(year, day, is_timed, is_release, part, limits, Echo::Buffer)
+                            .unwrap(),
+                    )
+                })
+                .collect()
+        });
+
+        for (day, (day_reports, output)) in results {
+            print_header(day, format);
+            output.print(echo);
+            collect(day, day_reports);
         }
-    });
+    }
+
+    if format == Format::Json {
+        print_json(&reports, &days);
+    } else {
+        print_summary(&reports, &days);
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if format == Format::Human {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+
+        if let Err(e) = bench_history::append(year, &Run::new(is_release, reports)) {
+            eprintln!("Failed to record benchmark history: {e}");
+        }
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+            let result =
+                readme_benchmarks::Options::from_config(year, config::get()).and_then(|options| {
+                    readme_benchmarks::update(year, timings, total_millis, &options)
+                });
+
+            match result {
+                Ok(()) if format == Format::Human => {
+                    println!("Successfully updated README with benchmarks.");
+                }
+                Ok(()) => {}
+                Err(e) => {
+                    eprintln!("Failed to update readme with benchmarks: {e}");
                 }
             }
         }
     }
+
+    if days.iter().any(|(_, status)| status.is_failure()) {
+        process::exit(1);
+    }
+}
+
+/// Outcome of a day, summarised from the reports of its parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayStatus {
+    Ok,
+    NotScaffolded,
+    BuildFailed,
+    Panicked,
+    Errored,
+    TimedOut,
+    Killed,
+    Failed,
+}
+
+impl DayStatus {
+    #[must_use]
+    pub fn from_reports(reports: &[PartReport]) -> Self {
+        if reports.is_empty() {
+            return DayStatus::NotScaffolded;
+        }
+
+        // the most fundamental failure wins, a build failure affects every part.
+        [
+            (Status::BuildFailed, DayStatus::BuildFailed),
+            (Status::Panicked, DayStatus::Panicked),
+            (Status::Errored, DayStatus::Errored),
+            (Status::TimedOut, DayStatus::TimedOut),
+            (Status::Killed, DayStatus::Killed),
+            (Status::Failed, DayStatus::Failed),
+        ]
+        .into_iter()
+        .find(|(status, _)| reports.iter().any(|r| r.status == *status))
+        .map_or(DayStatus::Ok, |(_, day_status)| day_status)
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DayStatus::Ok => "ok",
+            DayStatus::NotScaffolded => "not-scaffolded",
+            DayStatus::BuildFailed => "build-failed",
+            DayStatus::Panicked => "panicked",
+            DayStatus::Errored => "errored",
+            DayStatus::TimedOut => "timed-out",
+            DayStatus::Killed => "killed",
+            DayStatus::Failed => "failed",
+        }
+    }
+
+    #[must_use]
+    pub fn is_failure(self) -> bool {
+        !matches!(self, DayStatus::Ok | DayStatus::NotScaffolded)
+    }
+}
+
+fn print_summary(reports: &[PartReport], days: &[(Day, DayStatus)]) {
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+
+    for (day, status) in days {
+        let errors: Vec<(u8, &str)> = reports
+            .iter()
+            .filter(|r| r.day == *day)
+            // multi-line errors, like parse errors with a snippet of the input, are summarised by their first line.
+            .filter_map(|r| Some((r.part, r.error.as_ref()?.lines().next()?)))
+            .collect();
+
+        // e.g. a build failure is the same for both parts.
+        let errors: Vec<String> = match errors.as_slice() {
+            [(_, a), (_, b)] if a == b => vec![(*a).to_string()],
+            errors => errors
+                .iter()
+                .map(|(part, error)| match part {
+                    0 => format!("parse: {error}"),
+                    part => format!("part {part}: {error}"),
+                })
+                .collect(),
+        };
+
+        let line = format!("Day {day}  {:<14}  {}", status.as_str(), errors.join("; "));
+        println!("{}", line.trim_end());
+    }
+
+    let mut counts: Vec<(DayStatus, usize)> = vec![];
+    for (_, status) in days {
+        match counts.iter_mut().find(|(s, _)| s == status) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*status, 1)),
+        }
+    }
+    counts.sort_by_key(|(status, _)| *status as u8);
+
+    println!();
+    println!(
+        "{}",
+        counts
+            .iter()
+            .map(|(status, count)| format!("{count} {}", status.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+}
+
+fn print_header(day: Day, format: Format) {
+    if format == Format::Human {
+        if day > 1 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    }
+}
+
+fn print_json(reports: &[PartReport], days: &[(Day, DayStatus)]) {
+    let mut obj: HashMap<String, JsonValue> = HashMap::new();
+    obj.insert(
+        "results".into(),
+        JsonValue::Array(reports.iter().map(PartReport::to_json).collect()),
+    );
+    obj.insert(
+        "days".into(),
+        JsonValue::Array(
+            days.iter()
+                .map(|(day, status)| {
+                    let mut day_obj: HashMap<String, JsonValue> = HashMap::new();
+                    day_obj.insert("day".into(), f64::from(day.into_inner()).into());
+                    day_obj.insert("status".into(), status.as_str().to_string().into());
+                    day_obj.into()
+                })
+                .collect(),
+        ),
+    );
+
+    match JsonValue::from(obj).format() {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("Failed to serialize results: {e}"),
+    }
+}
+
+/// Run the solution for a given day, in-process if it is linked into this binary and in a child process otherwise.
+/// Linked solutions pick up `--time` and `--part` from the arguments of this process instead, and are not
+/// subject to `limits`.
+pub(crate) fn run_day(
+    year: Year,
+    day: Day,
+    is_timed: bool,
+    is_release: bool,
+    part: Option<u8>,
+    limits: &Limits,
+    echo: Echo,
+) -> Result<(Vec<PartReport>, Buffered), Error> {
+    match registry::find(year, day) {
+        Some(solution) => Ok(run_in_process(solution, echo)),
+        None => child_commands::run_solution(year, day, is_timed, is_release, part, limits, echo),
+    }
+}
+
+/// Run a linked solution. Panics inside a part are reported by the runner. An input that cannot be read
+/// fails all parts.
+fn run_in_process(solution: &Solution, echo: Echo) -> (Vec<PartReport>, Buffered) {
+    let run = || match Source::Puzzle.read(solution.year, solution.day) {
+        Ok(input) => (solution.run)(&input),
+        Err(e) => {
+            let error = Source::Puzzle.explain(solution.day, &e);
+            [1, 2]
+                .into_iter()
+                .map(|part| {
+                    PartReport::unfinished(
+                        solution.year,
+                        solution.day,
+                        part,
+                        Status::Failed,
+                        Some(format!("could not read the input: {error}")),
+                    )
+                })
+                .collect()
+        }
+    };
+
+    if echo == Echo::Stdout {
+        return (run(), Buffered::default());
+    }
+
+    let (reports, output) = runner::capture(run);
+
+    let mut buffered = Buffered::default();
+    match echo {
+        Echo::Stdout | Echo::Discard => {}
+        Echo::Stderr => eprint!("{output}"),
+        Echo::Buffer => buffered.stdout = output,
+    }
+
+    (reports, buffered)
+}
+
+/// Where the output of a solution is forwarded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Echo {
+    Stdout,
+    Stderr,
+    Discard,
+    /// Hold back output, it is returned as [`Buffered`] instead.
+    Buffer,
+}
+
+/// Output of a solution that was held back with [`Echo::Buffer`].
+#[derive(Debug, Default)]
+pub struct Buffered {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Buffered {
+    /// Forward the held back output like it would have been forwarded with `echo`.
+    pub fn print(&self, echo: Echo) {
+        match echo {
+            Echo::Stdout => print!("{}", self.stdout),
+            Echo::Stderr => eprint!("{}", self.stdout),
+            Echo::Discard | Echo::Buffer => return,
+        }
+        eprint!("{}", self.stderr);
+    }
 }
 
 #[derive(Debug)]
@@ -56,134 +381,344 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the
+/// result records they write to the report file.
+pub(crate) mod child_commands {
+    use super::{get_bin_name, get_feature_args, get_path_for_bin, Buffered, Echo, Error};
+    use crate::template::answers::Answers;
+    use crate::template::limits::{self, Limits, RESUME_PART_ENV, TIMEOUT_EXIT_CODE};
+    use crate::template::report::{self, PartReport, Status, REPORT_FILE_ENV};
+    use crate::template::{ANSI_ITALIC, ANSI_RESET};
+    use crate::{Day, Year};
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day and return the result records of its parts.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        part: Option<u8>,
+        limits: &Limits,
+        echo: Echo,
+    ) -> Result<(Vec<PartReport>, Buffered), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return Ok((vec![], Buffered::default()));
+        }
+
+        // build separately, so a solution that does not compile is told apart from one that fails when run.
+        if let Some(reports) = build(year, day, is_release, part, echo)? {
+            return Ok(reports);
+        }
+
+        let started = Instant::now();
+        let (mut reports, mut output, mut status) =
+            run_child(year, day, is_timed, is_release, part, limits, None, echo)?;
+
+        // a part that runs past its timeout ends the process, part two still gets its own chance.
+        let part_one_timed_out = part.is_none()
+            && status.code() == Some(TIMEOUT_EXIT_CODE)
+            && reports
+                .iter()
+                .any(|r| r.part == 1 && r.status == Status::TimedOut)
+            && !reports.iter().any(|r| r.part == 2);
+
+        let remaining = limits
+            .day_timeout
+            .map(|timeout| timeout.saturating_sub(started.elapsed()));
+
+        if part_one_timed_out && remaining != Some(Duration::ZERO) {
+            let limits = Limits {
+                day_timeout: remaining,
+                ..*limits
+            };
+            let (resumed, resumed_output, resumed_status) = run_child(
+                year,
+                day,
+                is_timed,
+                is_release,
+                part,
+                &limits,
+                Some(2),
+                echo,
+            )?;
+            reports.extend(resumed.into_iter().filter(|r| r.part == 2));
+            output.stdout.push_str(&resumed_output.stdout);
+            output.stderr.push_str(&resumed_output.stderr);
+            status = resumed_status;
+        }
+
+        // parts that never got to run, because the process was ended before.
+        let missing = if status.code() == Some(TIMEOUT_EXIT_CODE) {
+            Some((Status::TimedOut, None))
+        } else if limits::was_killed(&status) {
+            Some((Status::Killed, Some(format!("ended by {status}"))))
+        } else if !status.success() {
+            Some((
+                Status::Failed,
+                status.code().map(|code| format!("exited with code {code}")),
+            ))
+        } else {
+            None
+        };
+
+        if let Some((missing_status, error)) = missing {
+            for missing in selected_parts(part) {
+                if reports.iter().any(|r| r.part == missing) {
+                    continue;
+                }
+
+                echo_line(
+                    &format!(
+                        "Part {missing}: {ANSI_ITALIC}{}{ANSI_RESET}",
+                        missing_status.label().unwrap()
+                    ),
+                    echo,
+                    &mut output,
+                );
+
+                reports.push(PartReport::unfinished(
+                    year,
+                    day,
+                    missing,
+                    missing_status,
+                    error.clone(),
+                ));
+            }
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        Ok((reports, output))
+    }
+
+    fn selected_parts(part: Option<u8>) -> Vec<u8> {
+        part.map_or(vec![1, 2], |part| vec![part])
+    }
+
+    fn echo_line(line: &str, echo: Echo, output: &mut Buffered) {
+        match echo {
+            Echo::Stdout => println!("{line}"),
+            Echo::Stderr => eprintln!("{line}"),
+            Echo::Discard => {}
+            Echo::Buffer => {
+                output.stdout.push_str(line);
+                output.stdout.push('\n');
+            }
+        }
+    }
+
+    /// Build the solution bin. Returns reports that mark the selected parts as [`Status::BuildFailed`] if it
+    /// does not compile.
+    fn build(
+        year: Year,
+        day: Day,
+        is_release: bool,
+        part: Option<u8>,
+        echo: Echo,
+    ) -> Result<Option<(Vec<PartReport>, Buffered)>, Error> {
+        let bin_name = get_bin_name(year, day);
+        let mut args = vec!["build", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        args.extend(get_feature_args());
+
+        let build = Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()?;
+
+        if build.status.success() {
+            return Ok(None);
+        }
+
+        let stderr = String::from_utf8_lossy(&build.stderr);
+        let mut output = Buffered::default();
+
+        match echo {
+            Echo::Stdout | Echo::Stderr => eprint!("{stderr}"),
+            Echo::Discard => {}
+            Echo::Buffer => output.stderr.push_str(&stderr),
+        }
+
+        // the first compiler error, e.g. "error[E0425]: cannot find value `x` in this scope".
+        let error = stderr
+            .lines()
+            .find(|line| line.starts_with("error"))
+            .map(ToString::to_string);
+
+        let mut reports = vec![];
+        for part in selected_parts(part) {
+            echo_line(
+                &format!("Part {part}: {ANSI_ITALIC}build failed{ANSI_RESET}"),
+                echo,
+                &mut output,
+            );
+            reports.push(PartReport::unfinished(
+                year,
+                day,
+                part,
+                Status::BuildFailed,
+                error.clone(),
+            ));
+        }
+
+        Ok(Some((reports, output)))
+    }
+
+    /// Run the solution bin once, resuming at `resume_part` if given.
+    #[allow(clippy::too_many_arguments)]
+    fn run_child(
+        year: Year,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        part: Option<u8>,
+        limits: &Limits,
+        resume_part: Option<u8>,
+        echo: Echo,
+    ) -> Result<(Vec<PartReport>, Buffered, ExitStatus), Error> {
+        let bin_name = get_bin_name(year, day);
+        let part = part.map(|part| part.to_string());
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        args.extend(get_feature_args());
+
+        // mirror `--time` and `--part` flags to child invocations.
+        args.push("--");
+
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(part) = &part {
+            args.push("--part");
+            args.push(part);
+        }
+
+        let report_path = env::temp_dir().join(format!("aoc-{}-{year}-{day}.jsonl", process::id()));
+        // a stale file would otherwise leak records of an earlier run.
+        let _ = fs::remove_file(&report_path);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr, results are collected from the report file.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args)
+            .env(REPORT_FILE_ENV, &report_path)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        limits.apply(&mut cmd);
+        if let Some(resume_part) = resume_part {
+            cmd.env(RESUME_PART_ENV, resume_part.to_string());
+        }
+
+        let mut cmd = cmd.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
+        let buffer_stderr = echo == Echo::Buffer;
         let thread = thread::spawn(move || {
+            let mut buffered = String::new();
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                if buffer_stderr {
+                    buffered.push_str(&line);
+                    buffered.push('\n');
+                } else {
+                    eprintln!("{line}");
+                }
             });
+            buffered
         });
 
+        let mut output = Buffered::default();
+
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match echo {
+                Echo::Stdout => println!("{line}"),
+                Echo::Stderr => eprintln!("{line}"),
+                Echo::Discard => {}
+                Echo::Buffer => {
+                    output.stdout.push_str(&line);
+                    output.stdout.push('\n');
+                }
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        output.stderr = thread.join().unwrap();
+        let status = cmd.wait()?;
 
-        Ok(output)
+        if !report_path.exists() {
+            return Ok((vec![], output, status));
+        }
+
+        let reports = report::read(&report_path).map_err(|e| Error::Parser(e.to_string()));
+        let _ = fs::remove_file(&report_path);
+        Ok((reports?, output, status))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    pub fn collect_timings(reports: &[PartReport], day: Day, answers: &Answers) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            verified: [None, None],
+            unfinished: [None, None],
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        for report in reports {
+            // part `0` is the parse step of a `Solution`, it counts towards the total of the day.
+            if report.part == 0 {
+                if report.status == Status::Solved {
+                    timings.parse = Some(report.stats);
+                    timings.total_nanos += report.stats.median.as_nanos() as f64;
                 }
+                continue;
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            if report.status.label().is_some() {
+                if let Some(unfinished) = timings.unfinished.get_mut(usize::from(report.part) - 1) {
+                    *unfinished = Some(report.status);
                 }
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
-
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+            match report.part {
+                1 => timings.part_1 = Some(report.stats),
+                2 => timings.part_2 = Some(report.stats),
+                _ => continue,
+            }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+            timings.verified[usize::from(report.part - 1)] = match &report.answer {
+                Some(answer) => answers.matches(report.part, answer),
+                None => answers.get(report.part).map(|_| false),
+            };
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+            timings.total_nanos += report.stats.median.as_nanos() as f64;
+        }
 
-        Some((str_timing, parsed_timing))
+        timings
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -202,53 +737,150 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::super::DayStatus;
+        use super::collect_timings;
+        use std::time::Duration;
+
+        use crate::template::alloc::Memory;
+        use crate::template::answers::Answers;
+        use crate::template::report::{PartReport, Status};
+        use crate::template::stats::Stats;
+        use crate::{day, year};
+
+        fn mock_report(part: u8, median: Duration) -> PartReport {
+            PartReport {
+                year: year!(2023),
+                day: day!(1),
+                part,
+                status: Status::Solved,
+                answer: Some("42".into()),
+                error: None,
+                stats: Stats::single(median),
+                memory: Memory::default(),
+            }
+        }
 
         #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
+        fn test_both_parts() {
+            let res = collect_timings(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    mock_report(1, Duration::from_nanos(74)),
+                    mock_report(2, Duration::from_millis(74)),
                 ],
                 day!(1),
+                &Answers::default(),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74000074_f64);
+            assert_eq!(res.part_1.unwrap().median, Duration::from_nanos(74));
+            assert_eq!(res.part_2.unwrap().median, Duration::from_millis(74));
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
+        fn test_missing_parts() {
+            let res = collect_timings(
+                &[mock_report(2, Duration::from_secs(2))],
                 day!(1),
+                &Answers::default(),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos, 2000000000_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap().median, Duration::from_secs(2));
         }
 
         #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
+        fn test_no_parts() {
+            let res = collect_timings(&[], day!(1), &Answers::default());
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn test_parse_step() {
+            let mut parse = mock_report(0, Duration::from_nanos(26));
+            parse.answer = None;
+
+            let res = collect_timings(
+                &[parse, mock_report(1, Duration::from_nanos(74))],
+                day!(1),
+                &Answers::default(),
+            );
+            assert_approx_eq!(res.total_nanos, 100_f64);
+            assert_eq!(res.parse.unwrap().median, Duration::from_nanos(26));
+            assert_eq!(res.unfinished, [None, None]);
+        }
+
+        #[test]
+        fn test_skipped_parts() {
+            let mut skipped = mock_report(2, Duration::ZERO);
+            skipped.status = Status::Skipped;
+            skipped.answer = None;
+
+            let res = collect_timings(
+                &[mock_report(1, Duration::from_nanos(74)), skipped],
+                day!(1),
+                &Answers::default(),
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.unfinished, [None, Some(Status::Skipped)]);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn test_verified_parts() {
+            let answers = Answers {
+                part_one: Some("42".into()),
+                part_two: Some("43".into()),
+            };
+            let res = collect_timings(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    mock_report(1, Duration::from_nanos(74)),
+                    mock_report(2, Duration::from_nanos(74)),
                 ],
                 day!(1),
+                &answers,
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.verified, [Some(true), Some(false)]);
+        }
+
+        #[test]
+        fn test_day_status() {
+            let with_status = |part: u8, status: Status| PartReport {
+                status,
+                ..mock_report(part, Duration::ZERO)
+            };
+
+            assert_eq!(DayStatus::from_reports(&[]), DayStatus::NotScaffolded);
+            assert_eq!(
+                DayStatus::from_reports(&[
+                    with_status(1, Status::Solved),
+                    with_status(2, Status::Skipped)
+                ]),
+                DayStatus::Ok
+            );
+            assert_eq!(
+                DayStatus::from_reports(&[
+                    with_status(1, Status::Solved),
+                    with_status(2, Status::Panicked)
+                ]),
+                DayStatus::Panicked
+            );
+            assert_eq!(
+                DayStatus::from_reports(&[
+                    with_status(1, Status::TimedOut),
+                    with_status(2, Status::Panicked)
+                ]),
+                DayStatus::Panicked
+            );
+            assert_eq!(
+                DayStatus::from_reports(&[
+                    with_status(1, Status::BuildFailed),
+                    with_status(2, Status::BuildFailed)
+                ]),
+                DayStatus::BuildFailed
+            );
+            assert!(!DayStatus::NotScaffolded.is_failure());
+            assert!(DayStatus::Failed.is_failure());
         }
     }
 }
@@ -0,0 +1,111 @@
+use std::process;
+
+use crate::template::bench_history::{self, Run};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::Year;
+
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+pub fn handle(year: Year, baseline: Option<String>, save: Option<String>, threshold: f64) {
+    let mut runs = match bench_history::read(year) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    if runs.is_empty() {
+        eprintln!(
+            "No benchmark history for {year} yet. Run `cargo time` or `cargo solve <day> --time` first."
+        );
+        process::exit(1);
+    }
+
+    if let Some(name) = save {
+        save_baseline(year, &mut runs, name);
+        return;
+    }
+
+    let (current, earlier) = runs.split_last().unwrap();
+
+    let previous = match &baseline {
+        Some(name) => earlier.iter().rev().find(|run| run.is_named(name)),
+        None => earlier.iter().rev().find(|run| {
+            run.release == current.release && !bench_history::compare(run, current).is_empty()
+        }),
+    };
+
+    let Some(previous) = previous else {
+        match baseline {
+            Some(name) if current.is_named(&name) => eprintln!(
+                "The latest run is \"{name}\" itself. Run the benchmarks again to compare against it."
+            ),
+            Some(name) => eprintln!("No run named \"{name}\" found in the benchmark history."),
+            None => eprintln!("No earlier run with comparable results found."),
+        }
+        process::exit(1);
+    };
+
+    println!(
+        "Comparing {} against {} (threshold {threshold}%).",
+        current.label(),
+        previous.label()
+    );
+    println!();
+
+    let deltas = bench_history::compare(previous, current);
+    let mut regressions = 0;
+
+    for delta in &deltas {
+        let is_regression = delta.is_regression(threshold);
+        if is_regression {
+            regressions += 1;
+        }
+
+        println!(
+            "Day {} part {}: {:>10} → {:>10} {:>+8.1}%{}",
+            delta.day,
+            delta.part,
+            format!("{:.1?}", delta.before),
+            format!("{:.1?}", delta.after),
+            delta.change,
+            if is_regression {
+                "  ⚠ regression"
+            } else {
+                ""
+            }
+        );
+    }
+
+    println!();
+
+    if regressions > 0 {
+        println!("{ANSI_BOLD}{regressions} regression(s) above {threshold}%.{ANSI_RESET}");
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}No regressions above {threshold}%.{ANSI_RESET}");
+}
+
+/// Names the latest run, so later runs can be compared against it with `--baseline <name>`.
+fn save_baseline(year: Year, runs: &mut [Run], name: String) {
+    // a name refers to a single run.
+    for run in runs.iter_mut() {
+        if run.name.as_deref() == Some(&name) {
+            run.name = None;
+        }
+    }
+
+    let latest = runs.last_mut().unwrap();
+    latest.name = Some(name);
+    let label = latest.label();
+
+    match bench_history::write(year, runs) {
+        Ok(()) => println!("Saved the latest run as {label}."),
+        Err(e) => {
+            eprintln!("Failed to write benchmark history: {e}");
+            process::exit(1);
+        }
+    }
+}
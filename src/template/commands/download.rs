@@ -1,15 +1,12 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, AocClient};
+use crate::{Day, Year};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(year: Year, day: Day) {
+    let result = AocClient::from_env().and_then(|client| aoc_client::download(&client, year, day));
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = result {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,316 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::{fs, process};
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::example_tests::set_expected;
+use crate::template::puzzle::Puzzle;
+use crate::template::{get_data_path, get_path_for_bin};
+use crate::{Day, Year};
+
+/// Lines of a block shown when listing the candidates.
+const PREVIEW_LINES: usize = 6;
+
+pub fn handle(year: Year, day: Day, block_one: Option<usize>, block_two: Option<usize>) {
+    let puzzle_path = get_puzzle_path(year, day);
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read \"{puzzle_path}\". Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let puzzle = Puzzle::parse(&markdown);
+
+    if puzzle.blocks.is_empty() {
+        eprintln!("No code blocks found in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    print_blocks(&puzzle);
+
+    let has_part_two = puzzle.sections.len() > 1;
+
+    let index = |n: usize| {
+        block_index(n).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    };
+
+    let block_one = block_one
+        .map(index)
+        .unwrap_or_else(|| choose_block(&puzzle, 1));
+    let block_two = has_part_two.then(|| {
+        block_two
+            .map(index)
+            .unwrap_or_else(|| choose_block(&puzzle, 2))
+    });
+
+    for block in [Some(block_one), block_two].into_iter().flatten() {
+        if block >= puzzle.blocks.len() {
+            eprintln!(
+                "There is no block {}, expecting a number from 1 to {}.",
+                block + 1,
+                puzzle.blocks.len()
+            );
+            process::exit(1);
+        }
+    }
+
+    let answer_one = prompt("Example answer for part 1", puzzle.default_answer(1));
+    let answer_two = if has_part_two {
+        prompt("Example answer for part 2", puzzle.default_answer(2))
+    } else {
+        None
+    };
+
+    let module_path = get_path_for_bin(year, day);
+    let module = fs::read_to_string(&module_path).ok();
+
+    // separate files are needed if the parts have different examples, or the tests already expect them.
+    let per_part = block_two.is_some_and(|block| block != block_one)
+        || module
+            .as_deref()
+            .is_some_and(|m| m.contains("read_file_part(\"examples\""));
+
+    let examples: Vec<(String, usize)> = if per_part {
+        vec![
+            (format!("{day}-1.txt"), block_one),
+            (format!("{day}-2.txt"), block_two.unwrap_or(block_one)),
+        ]
+    } else {
+        vec![(format!("{day}.txt"), block_one)]
+    };
+
+    for (file_name, block) in examples {
+        let path = get_data_path("examples", year, &file_name);
+        let mut contents = puzzle.blocks[block].clone();
+        contents.push('\n');
+
+        if let Err(e) = fs::write(&path, contents) {
+            eprintln!("Failed to write example file \"{path}\": {e}");
+            process::exit(1);
+        }
+        println!("Wrote example file \"{path}\"");
+    }
+
+    // days that use `example_tests!` read the expected values from sidecars next to the examples.
+    if module
+        .as_deref()
+        .is_some_and(|m| m.contains("example_tests!("))
+    {
+        let stems = if per_part {
+            [format!("{day}-1"), format!("{day}-2")]
+        } else {
+            [day.to_string(), day.to_string()]
+        };
+
+        for (stem, key, answer) in [
+            (&stems[0], "part_one", answer_one.as_deref()),
+            (&stems[1], "part_two", answer_two.as_deref()),
+        ] {
+            if let Some(answer) = answer {
+                write_expected(year, stem, key, answer);
+            }
+        }
+        return;
+    }
+
+    let Some(mut module) = module else {
+        println!("No solution found at \"{module_path}\", skipping tests.");
+        return;
+    };
+
+    for (part, test_fn, answer) in [
+        (1, "test_part_one", answer_one.as_deref()),
+        (2, "test_part_two", answer_two.as_deref()),
+    ] {
+        let example_part = per_part.then_some(part);
+        match fill_in_test(&module, test_fn, answer, example_part) {
+            Some(updated) => module = updated,
+            None if answer.is_some() => {
+                println!(
+                    "Could not update `{test_fn}`, please fill in the expected value by hand."
+                );
+            }
+            None => {}
+        }
+    }
+
+    if let Err(e) = fs::write(&module_path, module) {
+        eprintln!("Failed to update tests in \"{module_path}\": {e}");
+        process::exit(1);
+    }
+    println!("Updated tests in \"{module_path}\"");
+}
+
+fn write_expected(year: Year, stem: &str, key: &str, answer: &str) {
+    let path = get_data_path("examples", year, &format!("{stem}.expected"));
+    let sidecar = fs::read_to_string(&path).unwrap_or_default();
+
+    if let Err(e) = fs::write(&path, set_expected(&sidecar, key, answer)) {
+        eprintln!("Failed to write expectation file \"{path}\": {e}");
+        process::exit(1);
+    }
+    println!("Wrote {key} to \"{path}\"");
+}
+
+fn print_blocks(puzzle: &Puzzle) {
+    for (part, section) in puzzle.sections.iter().enumerate() {
+        for block in &section.blocks {
+            let lines: Vec<&str> = puzzle.blocks[*block].lines().collect();
+            println!(
+                "Block {} (part {}, {} lines):",
+                block + 1,
+                part + 1,
+                lines.len()
+            );
+            for line in lines.iter().take(PREVIEW_LINES) {
+                println!("    {line}");
+            }
+            if lines.len() > PREVIEW_LINES {
+                println!("    …");
+            }
+        }
+    }
+    println!();
+}
+
+/// Index of a block passed as `--block <n>`, which counts from 1 like the list of blocks.
+fn block_index(n: usize) -> Result<usize, String> {
+    n.checked_sub(1)
+        .ok_or_else(|| "There is no block 0, blocks are numbered from 1.".into())
+}
+
+fn choose_block(puzzle: &Puzzle, part: u8) -> usize {
+    let default = puzzle.default_block(part).unwrap_or(0);
+
+    loop {
+        let answer = prompt(
+            &format!("Example block for part {part}"),
+            Some(&(default + 1).to_string()),
+        );
+        match answer.as_deref().map(str::parse::<usize>) {
+            Some(Ok(n)) if (1..=puzzle.blocks.len()).contains(&n) => return n - 1,
+            _ => println!(
+                "Expecting a block number from 1 to {}.",
+                puzzle.blocks.len()
+            ),
+        }
+    }
+}
+
+/// Asks a question on the terminal. An empty reply accepts the default, `-` clears it.
+/// Without a terminal, the default is used.
+fn prompt(question: &str, default: Option<&str>) -> Option<String> {
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() {
+        return default.map(ToString::to_string);
+    }
+
+    print!("{question} [{}]: ", default.unwrap_or("none"));
+    let _ = io::stdout().flush();
+
+    let mut reply = String::new();
+    if stdin.lock().read_line(&mut reply).is_err() {
+        return default.map(ToString::to_string);
+    }
+
+    match reply.trim() {
+        "" => default.map(ToString::to_string),
+        "-" => None,
+        reply => Some(reply.to_string()),
+    }
+}
+
+/// Updates a test generated by `scaffold`: sets the expected value of an unfilled assertion and
+/// switches the test to a per-part example file. Returns [`None`] if nothing could be updated.
+fn fill_in_test(
+    module: &str,
+    test_fn: &str,
+    answer: Option<&str>,
+    example_part: Option<u8>,
+) -> Option<String> {
+    let start = module.find(&format!("fn {test_fn}()"))?;
+    let end = module[start + 1..]
+        .find("fn test_")
+        .map_or(module.len(), |i| start + 1 + i);
+
+    let mut body = module[start..end].to_string();
+    let original = body.clone();
+
+    if let Some(answer) = answer {
+        let expected = if answer.parse::<i128>().is_ok() {
+            answer.to_string()
+        } else {
+            format!("{answer:?}.to_string()")
+        };
+        body = body.replacen(
+            "assert_eq!(result, None);",
+            &format!("assert_eq!(result, Some({expected}));"),
+            1,
+        );
+    }
+
+    if let Some(part) = example_part {
+        body = body.replacen(
+            "read_file(\"examples\", YEAR, DAY)",
+            &format!("read_file_part(\"examples\", YEAR, DAY, {part})"),
+            1,
+        );
+    }
+
+    (body != original).then(|| format!("{}{body}{}", &module[..start], &module[end..]))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{block_index, fill_in_test};
+
+    const MODULE: &str = r#"
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+"#;
+
+    #[test]
+    fn fills_in_expected_values() {
+        let module = fill_in_test(MODULE, "test_part_two", Some("281"), None).unwrap();
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(module.contains("assert_eq!(result, Some(281));"));
+
+        let module = fill_in_test(&module, "test_part_one", Some("AB"), None).unwrap();
+        assert!(module.contains(r#"assert_eq!(result, Some("AB".to_string()));"#));
+        assert!(!module.contains("None"));
+    }
+
+    #[test]
+    fn switches_to_part_examples() {
+        let module = fill_in_test(MODULE, "test_part_one", None, Some(1)).unwrap();
+        assert!(module.contains(r#"read_file_part("examples", YEAR, DAY, 1)"#));
+        assert!(module.contains(r#"read_file("examples", YEAR, DAY)"#));
+    }
+
+    #[test]
+    fn numbers_blocks_from_one() {
+        assert_eq!(block_index(1), Ok(0));
+        assert!(block_index(0).is_err());
+    }
+
+    #[test]
+    fn keeps_filled_tests() {
+        let module = fill_in_test(MODULE, "test_part_one", Some("1"), None).unwrap();
+        assert_eq!(
+            fill_in_test(&module, "test_part_one", Some("2"), None),
+            None
+        );
+    }
+}
@@ -1,5 +1,11 @@
 pub mod all;
+pub mod bench_compare;
 pub mod download;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod status;
+pub mod verify;
+pub mod watch;
@@ -1,16 +1,42 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, get_puzzle_path, AocClient};
+use crate::template::markdown;
+use crate::template::puzzle::part_text;
+use crate::{Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Shows the puzzle description in the terminal. A stored description is shown offline, the website is
+/// only asked if there is none yet, or part two is asked for and the stored one predates it.
+pub fn handle(year: Year, day: Day, part: Option<u8>) {
+    let path = get_puzzle_path(year, day);
+    let mut puzzle = fs::read_to_string(&path).ok();
+
+    let is_outdated = match &puzzle {
+        Some(puzzle) => part.is_some_and(|part| part_text(puzzle, part).is_none()),
+        None => true,
+    };
+
+    if is_outdated {
+        match AocClient::from_env().and_then(|client| aoc_client::read(&client, year, day)) {
+            Ok(downloaded) => puzzle = Some(downloaded),
+            Err(e) if puzzle.is_none() => {
+                eprintln!("failed to read day {day}: no description stored at \"{path}\" and downloading it failed: {e}");
+                process::exit(1);
+            }
+            // offline, stick with the stored description.
+            Err(_) => {}
+        }
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let puzzle = puzzle.unwrap();
+
+    let text = match part {
+        Some(part) => part_text(&puzzle, part).unwrap_or_else(|| {
+            eprintln!("Part {part} of day {day} is not unlocked yet, solve part one first.");
+            process::exit(1);
+        }),
+        None => &puzzle,
     };
+
+    print!("{}", markdown::render(text, markdown::terminal_width()));
 }
@@ -1,51 +1,132 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{get_data_path, get_path_for_bin};
+use crate::{Day, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Directory of user-defined templates, relative to the project root.
+const TEMPLATE_DIR: &str = "templates";
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// Templates that are available even if the project has no `templates` directory.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("default", include_str!("../../../templates/default.rs")),
+    ("grid", include_str!("../../../templates/grid.rs")),
+    ("nom", include_str!("../../../templates/nom.rs")),
+    (
+        "shared-parse",
+        include_str!("../../../templates/shared-parse.rs"),
+    ),
+    ("solution", include_str!("../../../templates/solution.rs")),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Name of the template, i.e. `templates/<name>.rs`.
+    pub template: String,
+    /// Type returned by both parts, e.g. `u64`.
+    pub answer_type: String,
+    /// Whether each part reads its own example file, e.g. `01-1.txt` and `01-2.txt`.
+    pub part_examples: bool,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            template: "default".into(),
+            answer_type: "u32".into(),
+            part_examples: false,
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Loads a template from the project's template directory, falling back to the built-in templates.
+fn load_template(name: &str) -> Result<String, String> {
+    let path = Path::new(TEMPLATE_DIR).join(format!("{name}.rs"));
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    if path.exists() {
+        return fs::read_to_string(&path)
+            .map_err(|e| format!("could not read template \"{}\": {e}", path.display()));
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            let builtins: Vec<_> = BUILTIN_TEMPLATES.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown template \"{name}\", add it as \"{}\" or use one of: {}",
+                path.display(),
+                builtins.join(", ")
+            )
+        })
+}
+
+/// Fills in the placeholders of a template:
+///  - `{{year}}` and `{{day}}`: the year and the day number.
+///  - `{{answer_type}}`: the type returned by both parts.
+///  - `{{example_part_one}}` and `{{example_part_two}}`: an expression that reads the example of a part.
+fn render(template: &str, year: Year, day: Day, options: &Options) -> String {
+    let example = |part: u8| {
+        if options.part_examples {
+            format!("advent_of_code::template::read_file_part(\"examples\", YEAR, DAY, {part})")
+        } else {
+            "advent_of_code::template::read_file(\"examples\", YEAR, DAY)".to_string()
+        }
+    };
+
+    template
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.into_inner().to_string())
+        .replace("{{answer_type}}", &options.answer_type)
+        .replace("{{example_part_one}}", &example(1))
+        .replace("{{example_part_two}}", &example(2))
 }
-"#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    // keep the contents of files that were downloaded before scaffolding.
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(year: Year, day: Day, options: &Options) {
+    let input_path = get_data_path("inputs", year, &format!("{day}.txt"));
+    let module_path = get_path_for_bin(year, day);
+
+    let example_paths = if options.part_examples {
+        vec![
+            get_data_path("examples", year, &format!("{day}-1.txt")),
+            get_data_path("examples", year, &format!("{day}-2.txt")),
+        ]
+    } else {
+        vec![get_data_path("examples", year, &format!("{day}.txt"))]
+    };
+
+    let template = match load_template(&options.template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    for dir in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(get_data_path(dir, year, "")) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,11 +136,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render(&template, year, day, options).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -79,16 +156,95 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    for example_path in example_paths {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load_template, render, Options, BUILTIN_TEMPLATES};
+    use crate::{day, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let options = Options {
+            answer_type: "u64".into(),
+            ..Options::default()
+        };
+        let module = render(
+            &load_template("default").unwrap(),
+            year!(2023),
+            day!(8),
+            &options,
+        );
+
+        assert!(module.starts_with("advent_of_code::solution!(2023, 8);"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+        assert!(module.ends_with("advent_of_code::example_tests!();\n"));
+        assert!(!module.contains("{{"));
+    }
+
+    #[test]
+    fn renders_part_examples() {
+        let options = Options {
+            part_examples: true,
+            ..Options::default()
+        };
+        let module = render(
+            "part_one(&{{example_part_one}});\npart_two(&{{example_part_two}});",
+            year!(2023),
+            day!(8),
+            &options,
+        );
+
+        assert!(module.contains("read_file_part(\"examples\", YEAR, DAY, 1)"));
+        assert!(module.contains("read_file_part(\"examples\", YEAR, DAY, 2)"));
+    }
+
+    #[test]
+    fn builtin_templates_use_all_placeholders() {
+        for (name, template) in BUILTIN_TEMPLATES {
+            for placeholder in ["{{year}}", "{{day}}", "{{answer_type}}"] {
+                assert!(template.contains(placeholder), "{name} lacks {placeholder}");
+            }
+            assert!(
+                template.contains("advent_of_code::example_tests!("),
+                "{name} does not use example_tests!"
+            );
+            assert!(!template.contains("mod tests"), "{name} has its own tests");
+        }
+    }
+
+    #[test]
+    fn builtin_templates_do_not_read_placeholder_examples() {
+        // `scaffold` creates empty examples, which `read_file` rejects.
+        for (name, template) in BUILTIN_TEMPLATES {
+            for part_examples in [false, true] {
+                let options = Options {
+                    part_examples,
+                    ..Options::default()
+                };
+                let module = render(template, year!(2023), day!(8), &options);
+                assert!(!module.contains("read_file"), "{name} reads an example");
+            }
+        }
+    }
+
+    #[test]
+    fn errors_on_unknown_template() {
+        let e = load_template("does-not-exist").unwrap_err();
+        assert!(e.contains("grid, nom, shared-parse"));
+    }
 }
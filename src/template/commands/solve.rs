@@ -1,14 +1,46 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::{env, fs};
 
-use crate::Day;
+use crate::template::bench_history::{self, Run};
+use crate::template::input::Source;
+use crate::template::report::{self, PartReport, REPORT_FILE_ENV};
+use crate::template::{get_bin_name, get_feature_args, registry};
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    part: Option<u8>,
+    input: &Source,
+) {
+    // linked solutions pick up `--time`, `--submit`, `--part` and the input flags from the arguments of this process.
+    if let Some(solution) = registry::find(year, day) {
+        let reports = (solution.run)(&input.read_or_exit(year, day));
+        let failed = reports.iter().any(|r| r.status.is_failure());
+        if time && input.is_puzzle() {
+            record_history(year, release, reports);
+        }
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        get_bin_name(year, day),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    cmd_args.extend(get_feature_args().into_iter().map(ToString::to_string));
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -20,12 +52,48 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    let report_path = env::temp_dir().join(format!("aoc-{}-{year}-{day}.jsonl", process::id()));
+    let _ = fs::remove_file(&report_path);
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args)
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .stderr(Stdio::inherit());
+
+    if time && input.is_puzzle() {
+        // timed runs on the puzzle input are recorded in the benchmark history.
+        cmd.env(REPORT_FILE_ENV, &report_path);
+    }
 
-    cmd.wait().unwrap();
+    let status = cmd.spawn().unwrap().wait().unwrap();
+
+    if time && report_path.exists() {
+        match report::read(&report_path) {
+            Ok(reports) => record_history(year, release, reports),
+            Err(e) => eprintln!("Failed to read result records: {e}"),
+        }
+        let _ = fs::remove_file(&report_path);
+    }
+
+    // a failing build or solution fails the command, so scripts can rely on the exit code.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+fn record_history(year: Year, release: bool, reports: Vec<PartReport>) {
+    if reports.is_empty() {
+        return;
+    }
+
+    if let Err(e) = bench_history::append(year, &Run::new(release, reports)) {
+        eprintln!("Failed to record benchmark history: {e}");
+    }
 }
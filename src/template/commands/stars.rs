@@ -0,0 +1,14 @@
+use std::process;
+
+use crate::template::readme_stars;
+use crate::Year;
+
+pub fn handle(year: Year) {
+    match readme_stars::update(year) {
+        Ok(stars) => println!("Successfully updated README with {stars} stars."),
+        Err(e) => {
+            eprintln!("Failed to update readme with stars: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,460 @@
+/// Dashboard of where every day of a year stands, built from local data.
+///
+/// Per day, it shows whether the solution is scaffolded, the input is downloaded, examples are
+/// extracted and their tests pass, the answers are recorded and verified by the latest benchmark run,
+/// and how long that run took.
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::template::answers::{self, Answers};
+use crate::template::bench_history::{self, Run};
+use crate::template::commands::watch::parse_test_output;
+use crate::template::input::{self, Source};
+use crate::template::report::Status;
+use crate::template::{
+    get_bin_name, get_data_path, get_feature_args, get_path_for_bin, ANSI_BOLD, ANSI_GREEN,
+    ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
+use crate::{all_days, Day, Year};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Present,
+    Missing,
+    Empty,
+    Html,
+    Unreadable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tests {
+    /// Not run, because the day is not scaffolded or `--no-tests` was passed.
+    Skipped,
+    BuildFailed,
+    Ran {
+        passed: usize,
+        total: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Missing,
+    /// Recorded, but no benchmark run produced an answer for this part.
+    Recorded,
+    /// The latest benchmark run produced the recorded answer.
+    Verified,
+    /// The latest benchmark run produced a different answer.
+    Mismatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DayState {
+    day: Day,
+    scaffolded: bool,
+    input: Input,
+    examples: usize,
+    tests: Tests,
+    answers: [Answer; 2],
+    /// Time of the parse step and both parts in the latest benchmark run of the day.
+    benchmark: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    None,
+    Green,
+    Yellow,
+    Red,
+}
+
+/// A cell of the table, coloured in the terminal only.
+type Cell = (String, Color);
+
+pub fn handle(year: Year, markdown: bool, run_tests: bool) {
+    let history = bench_history::read(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history, leaving out benchmarks: {e}");
+        vec![]
+    });
+
+    let states: Vec<DayState> = all_days()
+        .map(|day| collect(year, day, &history, run_tests))
+        .collect();
+
+    if markdown {
+        println!("{}", render_markdown(&states));
+    } else {
+        println!("{}", render_terminal(&states));
+        println!();
+        println!("{ANSI_BOLD}{}{ANSI_RESET}", summarize(&states));
+    }
+}
+
+fn collect(year: Year, day: Day, history: &[Run], run_tests: bool) -> DayState {
+    let scaffolded = Path::new(&get_path_for_bin(year, day)).exists();
+
+    let input = match Source::Puzzle.read(year, day) {
+        Ok(_) => Input::Present,
+        Err(input::Error::Missing) => Input::Missing,
+        Err(input::Error::Empty) => Input::Empty,
+        Err(input::Error::Html) => Input::Html,
+        Err(input::Error::IO(_)) => Input::Unreadable,
+    };
+
+    let tests = if scaffolded && run_tests {
+        eprint!("\rRunning the tests of day {day}...");
+        let tests = run_day_tests(year, day);
+        eprint!("\r{}\r", " ".repeat(40));
+        tests
+    } else {
+        Tests::Skipped
+    };
+
+    let answers = answers::read(year, day).unwrap_or_default();
+    let latest = latest_run(history, day);
+
+    DayState {
+        day,
+        scaffolded,
+        input,
+        examples: count_examples(year, day),
+        tests,
+        answers: [1, 2].map(|part| answer_state(&answers, latest, day, part)),
+        benchmark: latest.map(|run| benchmark_time(run, day)),
+    }
+}
+
+/// Number of readable examples of a day, `{day}.txt` and `{day}-{n}.txt`.
+fn count_examples(year: Year, day: Day) -> usize {
+    let dir = get_data_path("examples", year, "");
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let day = day.to_string();
+    entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_name()?.to_str()?.strip_suffix(".txt")?;
+            let is_example = stem == day
+                || stem
+                    .strip_prefix(&day)
+                    .and_then(|s| s.strip_prefix('-'))
+                    .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+            (is_example && input::read_path(&path).is_ok()).then_some(())
+        })
+        .count()
+}
+
+fn run_day_tests(year: Year, day: Day) -> Tests {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--bin", &get_bin_name(year, day)])
+        .args(get_feature_args())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    let Ok(output) = cmd.output() else {
+        return Tests::BuildFailed;
+    };
+
+    let results = parse_test_output(&String::from_utf8_lossy(&output.stdout));
+    if results.is_empty() && !output.status.success() {
+        return Tests::BuildFailed;
+    }
+
+    count_tests(&results)
+}
+
+fn count_tests(results: &[(String, String)]) -> Tests {
+    let ran = results.iter().filter(|(_, outcome)| outcome != "ignored");
+    Tests::Ran {
+        passed: ran.clone().filter(|(_, outcome)| outcome == "ok").count(),
+        total: ran.count(),
+    }
+}
+
+/// The latest run of the benchmark history with results of the day.
+fn latest_run(history: &[Run], day: Day) -> Option<&Run> {
+    history
+        .iter()
+        .rev()
+        .find(|run| run.results.iter().any(|r| r.day == day))
+}
+
+fn answer_state(answers: &Answers, latest: Option<&Run>, day: Day, part: u8) -> Answer {
+    if answers.get(part).is_none() {
+        return Answer::Missing;
+    }
+
+    let answer = latest.and_then(|run| {
+        run.results
+            .iter()
+            .find(|r| r.day == day && r.part == part)?
+            .answer
+            .as_deref()
+    });
+
+    match answer.and_then(|answer| answers.matches(part, answer)) {
+        Some(true) => Answer::Verified,
+        Some(false) => Answer::Mismatch,
+        None => Answer::Recorded,
+    }
+}
+
+fn benchmark_time(run: &Run, day: Day) -> Duration {
+    run.results
+        .iter()
+        .filter(|r| r.day == day && r.status == Status::Solved)
+        .map(|r| r.stats.median)
+        .sum()
+}
+
+fn check(ok: bool) -> Cell {
+    if ok {
+        ("✔".into(), Color::Green)
+    } else {
+        ("✖".into(), Color::Red)
+    }
+}
+
+fn cells(state: &DayState) -> Vec<Cell> {
+    let input = match state.input {
+        Input::Present => check(true),
+        Input::Missing => ("missing".into(), Color::Red),
+        Input::Empty => ("empty".into(), Color::Red),
+        Input::Html => ("html page".into(), Color::Red),
+        Input::Unreadable => ("unreadable".into(), Color::Red),
+    };
+
+    let examples = match state.examples {
+        0 => ("none".into(), Color::Red),
+        n => (n.to_string(), Color::Green),
+    };
+
+    let tests = match state.tests {
+        Tests::Skipped => ("-".into(), Color::None),
+        Tests::BuildFailed => ("build failed".into(), Color::Red),
+        Tests::Ran { total: 0, .. } => ("none".into(), Color::Yellow),
+        Tests::Ran { passed, total } if passed == total => {
+            (format!("{passed}/{total} ✔"), Color::Green)
+        }
+        Tests::Ran { passed, total } => (format!("{passed}/{total} ✖"), Color::Red),
+    };
+
+    let answer = |answer: Answer| match answer {
+        Answer::Missing => ("-".into(), Color::None),
+        Answer::Recorded => ("recorded".into(), Color::Yellow),
+        Answer::Verified => ("verified ✔".into(), Color::Green),
+        Answer::Mismatch => ("mismatch ✖".into(), Color::Red),
+    };
+
+    let benchmark = match state.benchmark {
+        Some(time) => (format!("{time:.1?}"), Color::None),
+        None => ("-".into(), Color::None),
+    };
+
+    vec![
+        (format!("Day {}", state.day), Color::None),
+        check(state.scaffolded),
+        input,
+        examples,
+        tests,
+        answer(state.answers[0]),
+        answer(state.answers[1]),
+        benchmark,
+    ]
+}
+
+const HEADER: [&str; 8] = [
+    "Day",
+    "Solution",
+    "Input",
+    "Examples",
+    "Tests",
+    "Part 1",
+    "Part 2",
+    "Benchmark",
+];
+
+fn render_terminal(states: &[DayState]) -> String {
+    let rows: Vec<Vec<Cell>> = states.iter().map(cells).collect();
+
+    // widths are counted in characters, the colour codes take no space.
+    let widths: Vec<usize> = HEADER
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .map(|row| row[i].0.chars().count())
+                .chain([title.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut lines = vec![HEADER
+        .iter()
+        .zip(&widths)
+        .map(|(title, width)| format!("{ANSI_BOLD}{title:width$}{ANSI_RESET}"))
+        .collect::<Vec<_>>()
+        .join("  ")];
+
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|((text, color), width)| {
+                let color = match color {
+                    Color::None => return format!("{text:width$}"),
+                    Color::Green => ANSI_GREEN,
+                    Color::Yellow => ANSI_YELLOW,
+                    Color::Red => ANSI_RED,
+                };
+                format!("{color}{text:width$}{ANSI_RESET}")
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+fn render_markdown(states: &[DayState]) -> String {
+    let mut lines = vec![
+        format!("| {} |", HEADER.join(" | ")),
+        format!("| {} |", [":---:"; HEADER.len()].join(" | ")),
+    ];
+
+    for state in states {
+        let row: Vec<String> = cells(state).into_iter().map(|(text, _)| text).collect();
+        lines.push(format!("| {} |", row.join(" | ")));
+    }
+
+    lines.join("\n")
+}
+
+fn summarize(states: &[DayState]) -> String {
+    let count = |f: &dyn Fn(&DayState) -> bool| states.iter().filter(|s| f(s)).count();
+
+    let answers = states.iter().flat_map(|s| s.answers);
+    let recorded = answers.clone().filter(|a| *a != Answer::Missing).count();
+    let verified = answers.filter(|a| *a == Answer::Verified).count();
+
+    format!(
+        "{} scaffolded, {} inputs, {} with passing tests, {verified}/{recorded} answers verified.",
+        count(&|s| s.scaffolded),
+        count(&|s| s.input == Input::Present),
+        count(&|s| matches!(s.tests, Tests::Ran { passed, total } if total > 0 && passed == total)),
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        answer_state, count_tests, render_markdown, summarize, Answer, DayState, Input, Tests,
+    };
+    use crate::template::answers::Answers;
+    use crate::template::bench_history::Run;
+    use crate::template::report::{PartReport, Status};
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+
+    fn state() -> DayState {
+        DayState {
+            day: day!(5),
+            scaffolded: true,
+            input: Input::Present,
+            examples: 2,
+            tests: Tests::Ran {
+                passed: 2,
+                total: 2,
+            },
+            answers: [Answer::Verified, Answer::Recorded],
+            benchmark: Some(Duration::from_micros(1500)),
+        }
+    }
+
+    #[test]
+    fn counts_tests() {
+        let results = [
+            ("a".to_string(), "ok".to_string()),
+            ("b".to_string(), "FAILED, got Some(1)".to_string()),
+            ("c".to_string(), "ignored".to_string()),
+        ];
+        assert_eq!(
+            count_tests(&results),
+            Tests::Ran {
+                passed: 1,
+                total: 2
+            }
+        );
+    }
+
+    #[test]
+    fn verifies_answers_against_latest_run() {
+        let answers = Answers {
+            part_one: Some("35".into()),
+            part_two: Some("46".into()),
+        };
+        let report = |part: u8, answer: &str| PartReport {
+            answer: Some(answer.into()),
+            stats: Stats::from_samples(&[Duration::from_millis(1)]),
+            ..PartReport::unfinished(year!(2023), day!(5), part, Status::Solved, None)
+        };
+        let run = Run {
+            commit: None,
+            timestamp: 0,
+            release: true,
+            name: None,
+            results: vec![report(1, "35"), report(2, "45")],
+        };
+
+        assert_eq!(
+            answer_state(&answers, Some(&run), day!(5), 1),
+            Answer::Verified
+        );
+        assert_eq!(
+            answer_state(&answers, Some(&run), day!(5), 2),
+            Answer::Mismatch
+        );
+        assert_eq!(answer_state(&answers, None, day!(5), 1), Answer::Recorded);
+        assert_eq!(
+            answer_state(&Answers::default(), Some(&run), day!(5), 1),
+            Answer::Missing
+        );
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let missing = DayState {
+            day: day!(24),
+            scaffolded: false,
+            input: Input::Missing,
+            examples: 0,
+            tests: Tests::Skipped,
+            answers: [Answer::Recorded, Answer::Missing],
+            benchmark: None,
+        };
+
+        assert_eq!(
+            render_markdown(&[state(), missing]),
+            "| Day | Solution | Input | Examples | Tests | Part 1 | Part 2 | Benchmark |\n\
+             | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |\n\
+             | Day 05 | ✔ | ✔ | 2 | 2/2 ✔ | verified ✔ | recorded | 1.5ms |\n\
+             | Day 24 | ✖ | missing | none | - | recorded | - | - |"
+        );
+    }
+
+    #[test]
+    fn summarizes() {
+        assert_eq!(
+            summarize(&[state()]),
+            "1 scaffolded, 1 inputs, 1 with passing tests, 1/2 answers verified."
+        );
+    }
+}
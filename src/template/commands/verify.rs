@@ -0,0 +1,190 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::answers::{self, Answers};
+use crate::template::commands::all::{run_day, Echo};
+use crate::template::get_path_for_bin;
+use crate::template::limits::Limits;
+use crate::template::report::PartReport;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Year};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// An answer is recorded, but the solution did not produce one.
+    Missing {
+        expected: String,
+    },
+    /// No answer is recorded for this part.
+    Unknown,
+}
+
+pub fn handle(year: Year, is_release: bool) {
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+
+    for day in all_days() {
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            continue;
+        }
+
+        let answers = match answers::read(year, day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                failed += 1;
+                continue;
+            }
+        };
+
+        let reports = match run_day(
+            year,
+            day,
+            false,
+            is_release,
+            None,
+            &Limits::default(),
+            Echo::Discard,
+        ) {
+            Ok((reports, _)) => reports,
+            Err(e) => {
+                eprintln!("Day {day}: failed to run solution: {e:?}");
+                vec![]
+            }
+        };
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+        for part in 1..=2 {
+            let outcome = check(&answers, &reports, part);
+
+            match &outcome {
+                Outcome::Pass => passed += 1,
+                Outcome::Fail { .. } | Outcome::Missing { .. } => failed += 1,
+                Outcome::Unknown => unknown += 1,
+            }
+
+            println!("Part {part}: {}", format_outcome(&outcome));
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}{passed} passed, {failed} failed, {unknown} unknown.{ANSI_RESET}");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn check(answers: &Answers, reports: &[PartReport], part: u8) -> Outcome {
+    let Some(expected) = answers.get(part) else {
+        return Outcome::Unknown;
+    };
+
+    let actual = reports
+        .iter()
+        .find(|r| r.part == part)
+        .and_then(|r| r.answer.as_deref());
+
+    match actual {
+        Some(actual) if answers.matches(part, actual) == Some(true) => Outcome::Pass,
+        Some(actual) => Outcome::Fail {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        },
+        None => Outcome::Missing {
+            expected: expected.to_string(),
+        },
+    }
+}
+
+fn format_outcome(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Pass => "✔ pass".into(),
+        Outcome::Fail { expected, actual } => {
+            format!("✖ fail (expected {expected}, got {actual})")
+        }
+        Outcome::Missing { expected } => format!("✖ fail (expected {expected}, got no answer)"),
+        Outcome::Unknown => "? unknown".into(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Outcome};
+    use crate::template::alloc::Memory;
+    use crate::template::answers::Answers;
+    use crate::template::report::{PartReport, Status};
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn mock_report(part: u8, answer: Option<&str>) -> PartReport {
+        PartReport {
+            year: year!(2023),
+            day: day!(1),
+            part,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            },
+            answer: answer.map(ToString::to_string),
+            error: None,
+            stats: Stats::single(Duration::ZERO),
+            memory: Memory::default(),
+        }
+    }
+
+    fn mock_answers() -> Answers {
+        Answers {
+            part_one: Some("35".into()),
+            part_two: None,
+        }
+    }
+
+    #[test]
+    fn passes_on_match() {
+        let reports = [mock_report(1, Some("35"))];
+        assert_eq!(check(&mock_answers(), &reports, 1), Outcome::Pass);
+    }
+
+    #[test]
+    fn fails_on_mismatch() {
+        let reports = [mock_report(1, Some("36"))];
+        assert_eq!(
+            check(&mock_answers(), &reports, 1),
+            Outcome::Fail {
+                expected: "35".into(),
+                actual: "36".into()
+            }
+        );
+    }
+
+    #[test]
+    fn fails_on_missing_result() {
+        let reports = [mock_report(1, None)];
+        assert_eq!(
+            check(&mock_answers(), &reports, 1),
+            Outcome::Missing {
+                expected: "35".into()
+            }
+        );
+        assert_eq!(
+            check(&mock_answers(), &[], 1),
+            Outcome::Missing {
+                expected: "35".into()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_without_answer() {
+        let reports = [mock_report(2, Some("46"))];
+        assert_eq!(check(&mock_answers(), &reports, 2), Outcome::Unknown);
+    }
+}
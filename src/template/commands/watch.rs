@@ -0,0 +1,339 @@
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{env, fs, thread};
+
+use crate::template::input::Source;
+use crate::template::report::{self, REPORT_FILE_ENV};
+use crate::template::{
+    get_bin_name, get_data_path, get_feature_args, get_path_for_bin, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, Year};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and size of each watched file, [`None`] if it does not exist.
+type Snapshot = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+/// Labelled results of one run, e.g. `("Part 1", "142")`.
+type Results = Vec<(String, String)>;
+
+/// Re-runs the solution of a day, or its example tests, whenever its source or data files change.
+pub fn handle(year: Year, day: Day, release: bool, examples: bool, input: &Source) {
+    let mut snapshot = take_snapshot(year, day, input);
+    let mut previous: Option<Results> = None;
+
+    println!(
+        "Watching {} and the data files of day {day}. Press Ctrl+C to stop.",
+        get_path_for_bin(year, day)
+    );
+
+    loop {
+        let results = if examples {
+            run_examples(year, day, release)
+        } else {
+            run_solution(year, day, release, input)
+        };
+
+        match results {
+            Some(results) => {
+                print_changes(previous.as_ref(), &results);
+                previous = Some(results);
+            }
+            None => println!("Run failed, waiting for changes."),
+        }
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = take_snapshot(year, day, input);
+            let changed = changed_paths(&snapshot, &next);
+            snapshot = next;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        println!();
+        println!(
+            "{ANSI_BOLD}── Changed: {} ──{ANSI_RESET}",
+            changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+fn watched_paths(year: Year, day: Day, input: &Source) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(year, day)),
+        PathBuf::from(get_data_path("inputs", year, &format!("{day}.txt"))),
+    ];
+
+    if let Source::File(path) = input {
+        paths.push(path.clone());
+    }
+
+    // matches both `01.txt` and per-part examples like `01-2.txt`.
+    let examples_dir = get_data_path("examples", year, "");
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(&day.to_string()) && name.ends_with(".txt")
+                    })
+            })
+            .collect();
+        examples.sort();
+        paths.extend(examples);
+    }
+
+    paths
+}
+
+fn take_snapshot(year: Year, day: Day, input: &Source) -> Snapshot {
+    watched_paths(year, day, input)
+        .into_iter()
+        .map(|path| {
+            let stamp = fs::metadata(&path)
+                .ok()
+                .and_then(|meta| Some((meta.modified().ok()?, meta.len())));
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// Paths that were added, removed or modified between two snapshots.
+fn changed_paths(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let stamp = |snapshot: &Snapshot, path: &Path| {
+        snapshot
+            .iter()
+            .find(|(p, _)| p == path)
+            .and_then(|(_, stamp)| *stamp)
+    };
+
+    let mut changed: Vec<PathBuf> = before
+        .iter()
+        .chain(after.iter())
+        .map(|(path, _)| path)
+        .filter(|path| stamp(before, path) != stamp(after, path))
+        .cloned()
+        .collect();
+
+    changed.sort();
+    changed.dedup();
+    changed
+}
+
+/// Builds and runs the solution, passing its output through. Returns the answer of each part.
+fn run_solution(year: Year, day: Day, release: bool, input: &Source) -> Option<Results> {
+    let report_path =
+        env::temp_dir().join(format!("aoc-watch-{}-{year}-{day}.jsonl", process::id()));
+    let _ = fs::remove_file(&report_path);
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--bin", &get_bin_name(year, day)]);
+    if release {
+        cmd.arg("--release");
+    }
+    cmd.args(get_feature_args());
+    cmd.arg("--").args(input.to_args());
+    cmd.env(REPORT_FILE_ENV, &report_path)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+
+    let status = cmd.status().ok()?;
+    let reports = report::read(&report_path).unwrap_or_default();
+    let _ = fs::remove_file(&report_path);
+
+    if !status.success() && reports.is_empty() {
+        return None;
+    }
+
+    Some(
+        reports
+            .into_iter()
+            // the parse step of a `Solution` has no answer to compare.
+            .filter(|report| report.part != 0)
+            .map(|report| {
+                (
+                    format!("Part {}", report.part),
+                    report.answer.unwrap_or_else(|| "✖".into()),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Builds and runs the tests of the solution. Returns the outcome of each test.
+fn run_examples(year: Year, day: Day, release: bool) -> Option<Results> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--bin", &get_bin_name(year, day)]);
+    if release {
+        cmd.arg("--release");
+    }
+    cmd.args(get_feature_args());
+    cmd.stdout(Stdio::piped()).stderr(Stdio::inherit());
+
+    let output = cmd.output().ok()?;
+    let results = parse_test_output(&String::from_utf8_lossy(&output.stdout));
+
+    if results.is_empty() {
+        return None;
+    }
+
+    for (name, outcome) in &results {
+        println!("{name}: {outcome}");
+    }
+
+    Some(results)
+}
+
+/// Extracts the outcome of each test from the output of `cargo test`.
+/// A failed assertion is summarised by the value the test produced.
+pub(crate) fn parse_test_output(stdout: &str) -> Results {
+    let mut results: Results = vec![];
+
+    for line in stdout.lines() {
+        let Some(rest) = line.strip_prefix("test ") else {
+            continue;
+        };
+        let Some((name, outcome)) = rest.split_once(" ... ") else {
+            continue;
+        };
+        let name = name.rsplit("::").next().unwrap_or(name);
+        results.push((name.to_string(), outcome.trim().to_string()));
+    }
+
+    // failures are detailed in sections like `---- tests::test_part_one stdout ----`.
+    let mut current: Option<&str> = None;
+    for line in stdout.lines() {
+        if let Some(section) = line
+            .strip_prefix("---- ")
+            .and_then(|s| s.strip_suffix(" stdout ----"))
+        {
+            current = Some(section.rsplit("::").next().unwrap_or(section));
+            continue;
+        }
+
+        let Some(name) = current else {
+            continue;
+        };
+
+        if let Some(left) = line.trim_start().strip_prefix("left:") {
+            if let Some((_, outcome)) = results.iter_mut().find(|(n, _)| n == name) {
+                *outcome = format!("FAILED, got {}", left.trim());
+            }
+            current = None;
+        }
+    }
+
+    results
+}
+
+fn print_changes(previous: Option<&Results>, results: &Results) {
+    let Some(previous) = previous else {
+        return;
+    };
+
+    let changes = diff(previous, results);
+
+    println!();
+    if changes.is_empty() {
+        println!("No changes since the last run.");
+    } else {
+        println!("{ANSI_BOLD}Changes since the last run:{ANSI_RESET}");
+        for change in changes {
+            println!("  {change}");
+        }
+    }
+}
+
+fn diff(previous: &Results, results: &Results) -> Vec<String> {
+    let lookup = |results: &Results, label: &str| {
+        results
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, value)| value.clone())
+    };
+
+    let mut changes = vec![];
+
+    for (label, value) in results {
+        match lookup(previous, label) {
+            Some(old) if old == *value => {}
+            Some(old) => changes.push(format!("{label}: {old} → {value}")),
+            None => changes.push(format!("{label}: {value} (new)")),
+        }
+    }
+
+    for (label, old) in previous {
+        if lookup(results, label).is_none() {
+            changes.push(format!("{label}: {old} → (missing)"));
+        }
+    }
+
+    changes
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff, parse_test_output};
+
+    fn results(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(l, v)| ((*l).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_test_output() {
+        let stdout = "
+running 2 tests
+test tests::test_part_two ... FAILED
+test tests::test_part_one ... ok
+
+failures:
+
+---- tests::test_part_two stdout ----
+thread 'tests::test_part_two' panicked at src/bin/2023-01.rs:56:9:
+assertion `left == right` failed
+  left: Some(280)
+ right: Some(281)
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+";
+
+        assert_eq!(
+            parse_test_output(stdout),
+            results(&[
+                ("test_part_two", "FAILED, got Some(280)"),
+                ("test_part_one", "ok")
+            ])
+        );
+    }
+
+    #[test]
+    fn diffs_results() {
+        let previous = results(&[("Part 1", "142"), ("Part 2", "✖")]);
+
+        assert!(diff(&previous, &previous).is_empty());
+        assert_eq!(
+            diff(&previous, &results(&[("Part 1", "142"), ("Part 2", "281")])),
+            vec!["Part 2: ✖ → 281"]
+        );
+        assert_eq!(
+            diff(&previous, &results(&[("Part 2", "✖"), ("Part 3", "1")])),
+            vec!["Part 3: 1 (new)", "Part 1: 142 → (missing)"]
+        );
+    }
+}
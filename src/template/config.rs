@@ -0,0 +1,403 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Every setting is optional. Command-line flags take precedence over environment variables, which
+/// take precedence over the config file:
+///
+/// ```toml
+/// # the default year, `--year` and `AOC_YEAR` override it.
+/// year = 2023
+///
+/// [paths]
+/// data = "data"
+/// readme = "README.md"
+/// session_file = "~/.adventofcode.session"
+///
+/// [readme]
+/// columns = ["spread"]
+/// chart = ".assets/benchmarks-{year}.svg"
+///
+/// [all]
+/// jobs = 4
+/// timeout = 10
+/// part_timeout = 5
+/// memory_limit = 2048
+/// format = "human"
+///
+/// [scaffold]
+/// template = "solution"
+/// answer = "u64"
+///
+/// [bench-compare]
+/// threshold = 5
+/// ```
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, fs, io};
+
+use toml::{Table, Value};
+
+use crate::Year;
+
+/// Path of the config file, relative to the project root.
+pub const CONFIG_FILE: &str = "aoc.toml";
+/// Selects a different config file.
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+/// Overrides `paths.data`.
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+/// Overrides `paths.readme`.
+pub const README_ENV: &str = "AOC_README";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed config file: {e}"),
+            Error::IO(e) => write!(f, "could not read config file: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub year: Option<Year>,
+    /// Directory with a folder of inputs, examples and answers per year.
+    pub data_dir: String,
+    /// The readme with the stars and benchmark tables.
+    pub readme: String,
+    pub session_file: Option<PathBuf>,
+    /// Optional columns of the readme benchmark table.
+    pub readme_columns: Option<Vec<String>>,
+    /// Path the readme runtime chart is written to, `{year}` is replaced with the year.
+    pub readme_chart: Option<String>,
+    pub all: AllDefaults,
+    pub scaffold: ScaffoldDefaults,
+    /// Relative change in percent above which `bench-compare` reports a regression.
+    pub bench_threshold: Option<f64>,
+}
+
+/// Defaults of the flags of `all`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AllDefaults {
+    /// Days solved concurrently, `0` uses one thread per CPU like `--jobs 0`.
+    pub jobs: Option<usize>,
+    /// Timeout of a day, in seconds.
+    pub timeout: Option<f64>,
+    /// Timeout of each part, in seconds.
+    pub part_timeout: Option<f64>,
+    /// Memory ceiling, in megabytes.
+    pub memory_limit: Option<u64>,
+    pub format: Option<String>,
+}
+
+/// Defaults of the flags of `scaffold`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScaffoldDefaults {
+    pub template: Option<String>,
+    pub answer: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            readme: "README.md".into(),
+            session_file: None,
+            readme_columns: None,
+            readme_chart: None,
+            all: AllDefaults::default(),
+            scaffold: ScaffoldDefaults::default(),
+            bench_threshold: None,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file, `aoc.toml` or the file at `AOC_CONFIG`, and applies the environment
+    /// variables that override it. A missing `aoc.toml` is the same as an empty one.
+    pub fn load() -> Result<Self, Error> {
+        let mut config = match env::var_os(CONFIG_ENV) {
+            Some(path) => parse(&fs::read_to_string(path)?)?,
+            None => match fs::read_to_string(CONFIG_FILE) {
+                Ok(s) => parse(&s)?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        if let Ok(dir) = env::var(DATA_DIR_ENV) {
+            config.data_dir = dir;
+        }
+        if let Ok(readme) = env::var(README_ENV) {
+            config.readme = readme;
+        }
+
+        Ok(config)
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Loads the config of this process, which [`get`] returns from then on.
+/// Fails on a malformed config file instead of falling back to the defaults.
+pub fn init() -> Result<&'static Config, Error> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The config of this process, loaded on first use. A malformed config file is reported once
+/// and ignored, `main` exits on it with [`init`] before running a command.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("Warning: {e}, using the defaults.");
+            Config::default()
+        })
+    })
+}
+
+/// Expands a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn parse(s: &str) -> Result<Config, Error> {
+    let table: Table = s.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+    let mut config = Config::default();
+
+    for (key, value) in &table {
+        match key.as_str() {
+            "year" => {
+                let year = integer(value, key)?;
+                config.year = u16::try_from(year)
+                    .ok()
+                    .and_then(Year::new)
+                    .map(Some)
+                    .ok_or_else(|| Error::Parser(format!("\"{year}\" is not a valid year")))?;
+            }
+            "paths" => {
+                for (key, value) in section(value, key)? {
+                    match key.as_str() {
+                        "data" => config.data_dir = string(value, key)?,
+                        "readme" => config.readme = string(value, key)?,
+                        "session_file" => {
+                            config.session_file = Some(expand_home(&string(value, key)?));
+                        }
+                        _ => return Err(unknown("paths", key)),
+                    }
+                }
+            }
+            "readme" => {
+                for (key, value) in section(value, key)? {
+                    match key.as_str() {
+                        "columns" => {
+                            let columns = value.as_array().ok_or_else(|| invalid(key))?;
+                            config.readme_columns = Some(
+                                columns
+                                    .iter()
+                                    .map(|column| string(column, key))
+                                    .collect::<Result<_, _>>()?,
+                            );
+                        }
+                        "chart" => config.readme_chart = Some(string(value, key)?),
+                        _ => return Err(unknown("readme", key)),
+                    }
+                }
+            }
+            "all" => {
+                for (key, value) in section(value, key)? {
+                    let all = &mut config.all;
+                    match key.as_str() {
+                        "jobs" => all.jobs = Some(non_negative(value, key)?),
+                        "timeout" => all.timeout = Some(seconds(value, key)?),
+                        "part_timeout" => all.part_timeout = Some(seconds(value, key)?),
+                        "memory_limit" => all.memory_limit = Some(positive(value, key)?),
+                        "format" => all.format = Some(string(value, key)?),
+                        _ => return Err(unknown("all", key)),
+                    }
+                }
+            }
+            "scaffold" => {
+                for (key, value) in section(value, key)? {
+                    match key.as_str() {
+                        "template" => config.scaffold.template = Some(string(value, key)?),
+                        "answer" => config.scaffold.answer = Some(string(value, key)?),
+                        _ => return Err(unknown("scaffold", key)),
+                    }
+                }
+            }
+            "bench-compare" => {
+                for (key, value) in section(value, key)? {
+                    match key.as_str() {
+                        "threshold" => config.bench_threshold = Some(float(value, key)?),
+                        _ => return Err(unknown("bench-compare", key)),
+                    }
+                }
+            }
+            _ => return Err(Error::Parser(format!("unknown key \"{key}\""))),
+        }
+    }
+
+    Ok(config)
+}
+
+fn unknown(section: &str, key: &str) -> Error {
+    Error::Parser(format!("unknown key \"{key}\" in [{section}]"))
+}
+
+fn invalid(key: &str) -> Error {
+    Error::Parser(format!("invalid value of \"{key}\""))
+}
+
+fn section<'a>(value: &'a Value, key: &str) -> Result<&'a Table, Error> {
+    value
+        .as_table()
+        .ok_or_else(|| Error::Parser(format!("\"{key}\" must be a section")))
+}
+
+fn string(value: &Value, key: &str) -> Result<String, Error> {
+    value
+        .as_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| invalid(key))
+}
+
+fn integer(value: &Value, key: &str) -> Result<i64, Error> {
+    value.as_integer().ok_or_else(|| invalid(key))
+}
+
+fn positive<T: TryFrom<i64>>(value: &Value, key: &str) -> Result<T, Error> {
+    let n = integer(value, key)?;
+    if n < 1 {
+        return Err(invalid(key));
+    }
+    T::try_from(n).map_err(|_| invalid(key))
+}
+
+fn non_negative<T: TryFrom<i64>>(value: &Value, key: &str) -> Result<T, Error> {
+    T::try_from(integer(value, key)?).map_err(|_| invalid(key))
+}
+
+fn float(value: &Value, key: &str) -> Result<f64, Error> {
+    match value {
+        Value::Integer(n) => Ok(*n as f64),
+        Value::Float(n) => Ok(*n),
+        _ => Err(invalid(key)),
+    }
+}
+
+fn seconds(value: &Value, key: &str) -> Result<f64, Error> {
+    match float(value, key)? {
+        secs if secs > 0.0 && secs.is_finite() => Ok(secs),
+        _ => Err(invalid(key)),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Config};
+    use crate::template::report::Format;
+    use crate::year;
+
+    #[test]
+    fn parses_empty_config() {
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = parse(
+            r#"
+            year = 2022
+
+            [paths]
+            data = "puzzles"
+            readme = "docs/README.md"
+
+            [readme]
+            columns = ["spread", "share"]
+
+            [all]
+            jobs = 4
+            timeout = 2.5
+            memory_limit = 512
+
+            [scaffold]
+            template = "solution"
+
+            [bench-compare]
+            threshold = 5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(year!(2022)));
+        assert_eq!(config.data_dir, "puzzles");
+        assert_eq!(config.readme, "docs/README.md");
+        assert_eq!(
+            config.readme_columns,
+            Some(vec!["spread".into(), "share".into()])
+        );
+        assert_eq!(config.all.jobs, Some(4));
+        assert_eq!(config.all.timeout, Some(2.5));
+        assert_eq!(config.all.memory_limit, Some(512));
+        assert_eq!(config.scaffold.template.as_deref(), Some("solution"));
+        assert_eq!(config.bench_threshold, Some(5.0));
+    }
+
+    #[test]
+    fn accepts_jobs_like_the_flag() {
+        // `--jobs 0` uses one thread per CPU, so does `jobs = 0`.
+        assert_eq!(parse("[all]\njobs = 0").unwrap().all.jobs, Some(0));
+    }
+
+    #[test]
+    fn parses_documented_settings() {
+        // the project's config with every commented out setting enabled.
+        let config: String = include_str!("../../aoc.toml")
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(setting) if setting.contains(" = ") => setting,
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let config = parse(&config).unwrap();
+
+        assert!(config.session_file.is_some());
+        assert!(config.all.jobs.is_some());
+        let format = config.all.format.unwrap();
+        assert!(format.parse::<Format>().is_ok(), "invalid format {format}");
+    }
+
+    #[test]
+    fn errors_on_invalid_config() {
+        assert!(parse("year = 2000").is_err());
+        assert!(parse("yaer = 2023").is_err());
+        assert!(parse("[all]\njobs = -1").is_err());
+        assert!(parse("[all]\nmemory_limit = 0").is_err());
+        assert!(parse("[all]\ntimeout = -1").is_err());
+        assert!(parse("[paths]\ninputs = \"x\"").is_err());
+        assert!(parse("paths = \"x\"").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+/// Support code of the tests that the `example_tests!` macro generates.
+///
+/// The build script generates a test for every example `data/{year}/examples/{day}.txt` or
+/// `{day}-{n}.txt` that has a sidecar file `{day}.expected` or `{day}-{n}.expected` next to it,
+/// one test per key of the sidecar:
+///
+/// ```toml
+/// part_one = 374
+/// part_two = "82000210"
+/// # a parameterised run, declared in the macro as `expansion_10 => |input| ...`.
+/// expansion_10 = 1030
+/// ```
+///
+/// Sidecars are read when the tests run, so only adding or removing examples and keys needs a rebuild.
+use std::fmt::Display;
+use std::{fs, io};
+
+use toml::Table;
+
+use crate::template::answers::parse_value;
+use crate::template::input;
+use crate::template::runner::PartResult;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed expectation file: {e}"),
+            Error::IO(e) => write!(f, "could not read expectation file: {e}"),
+        }
+    }
+}
+
+/// Path of the sidecar file of an example, e.g. `data/2023/examples/08-2.expected` for `08-2.txt`.
+#[must_use]
+pub fn expected_path(example: &str) -> String {
+    let stem = example.strip_suffix(".txt").unwrap_or(example);
+    format!("{stem}.expected")
+}
+
+/// Reads the expected answer of a part or parameterised run from a sidecar file.
+pub fn read_expected(path: &str, key: &str) -> Result<Option<String>, Error> {
+    parse_expected(&fs::read_to_string(path)?, key)
+}
+
+fn parse_expected(s: &str, key: &str) -> Result<Option<String>, Error> {
+    let table: Table = s.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+    parse_value(&table, key).map_err(|e| Error::Parser(e.to_string()))
+}
+
+/// Sets `key` of a sidecar to `answer`, keeping the other keys and comments.
+/// Answers that are numbers are written as integers, others as strings.
+#[must_use]
+pub fn set_expected(sidecar: &str, key: &str, answer: &str) -> String {
+    let value = if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        toml::Value::String(answer.to_string()).to_string()
+    };
+    let line = format!("{key} = {value}");
+
+    let mut lines: Vec<String> = sidecar.lines().map(str::to_string).collect();
+    let existing = lines.iter_mut().find(|l| {
+        l.split_once('=')
+            .is_some_and(|(k, _)| k.trim() == key && !l.trim_start().starts_with('#'))
+    });
+
+    match existing {
+        Some(existing) => *existing = line,
+        None => lines.push(line),
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// The answer of a part as a string, to compare it with the sidecar.
+///
+/// # Panics
+/// Panics with the error of a fallible part, so that a test shows why the part failed.
+pub fn answer<R: PartResult>(result: R) -> Option<String> {
+    match result.into_result() {
+        Ok(answer) => answer.map(|answer| answer.to_string()),
+        Err(e) => panic!("the part returned an error:\n{e}"),
+    }
+}
+
+/// Runs `run` on an example and compares its answer with the value of `key` in the sidecar of the example.
+///
+/// # Panics
+/// Panics if the answer does not match, or the example or its sidecar cannot be read.
+pub fn check(run: impl Fn(&str) -> Option<String>, example: &str, key: &str) {
+    let input = input::read_path(example)
+        .unwrap_or_else(|e| panic!("could not read example \"{example}\": {e}"));
+
+    let sidecar = expected_path(example);
+    let expected = match read_expected(&sidecar, key) {
+        Ok(Some(expected)) => expected,
+        Ok(None) => panic!("\"{key}\" is missing from \"{sidecar}\""),
+        Err(e) => panic!("{e} (\"{sidecar}\")"),
+    };
+
+    let actual = run(&input);
+    assert_eq!(
+        actual.as_deref().map(str::trim),
+        Some(expected.trim()),
+        "{key} of example \"{example}\""
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer, check, expected_path, parse_expected, set_expected};
+    use crate::template::parse::ParseError;
+
+    #[test]
+    fn derives_sidecar_paths() {
+        assert_eq!(
+            expected_path("data/2023/examples/08-2.txt"),
+            "data/2023/examples/08-2.expected"
+        );
+    }
+
+    #[test]
+    fn parses_parameterised_runs() {
+        let s = "part_one = 374\nexpansion_10 = \"1030\"\n";
+        assert_eq!(parse_expected(s, "part_one").unwrap(), Some("374".into()));
+        assert_eq!(
+            parse_expected(s, "expansion_10").unwrap(),
+            Some("1030".into())
+        );
+        assert_eq!(parse_expected(s, "part_two").unwrap(), None);
+        assert!(parse_expected("part_one = 1.5", "part_one").is_err());
+    }
+
+    #[test]
+    fn sets_expected_answers() {
+        let sidecar = set_expected("", "part_one", "374");
+        assert_eq!(sidecar, "part_one = 374\n");
+
+        let sidecar = set_expected(
+            "part_one = 1\n# expansion_10 = 1\nexpansion_10 = 1030\n",
+            "part_one",
+            "ABC",
+        );
+        assert_eq!(
+            sidecar,
+            "part_one = \"ABC\"\n# expansion_10 = 1\nexpansion_10 = 1030\n"
+        );
+        assert_eq!(
+            parse_expected(&sidecar, "part_one").unwrap(),
+            Some("ABC".into())
+        );
+    }
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(answer(Some(42)), Some("42".into()));
+        assert_eq!(answer(Ok::<Option<u8>, ParseError>(None)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the part returned an error")]
+    fn fails_on_errors() {
+        answer(Err::<Option<u8>, _>(ParseError::at_offset("x", 0, "oops")));
+    }
+
+    #[test]
+    #[should_panic(expected = "could not read example")]
+    fn fails_on_missing_examples() {
+        check(|_| None, "data/does-not-exist.txt", "part_one");
+    }
+}
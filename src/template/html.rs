@@ -0,0 +1,478 @@
+/// Minimal HTML handling for Advent of Code pages: a forgiving parser and a converter of puzzle descriptions to markdown.
+use std::fmt::Write;
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Text(String),
+    Element(Element),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    #[must_use]
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The concatenated text of all descendant text nodes.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let mut s = String::new();
+        collect_text(&self.children, &mut s);
+        s
+    }
+}
+
+fn collect_text(nodes: &[Node], s: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => s.push_str(t),
+            Node::Element(e) => collect_text(&e.children, s),
+        }
+    }
+}
+
+/// Depth-first search for all elements with the given tag name.
+/// Matching elements are not searched for nested matches.
+#[must_use]
+pub fn find_all<'a>(nodes: &'a [Node], name: &str) -> Vec<&'a Element> {
+    let mut found = vec![];
+    for node in nodes {
+        if let Node::Element(e) = node {
+            if e.name == name {
+                found.push(e);
+            } else {
+                found.extend(find_all(&e.children, name));
+            }
+        }
+    }
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses an HTML document into a tree. Unclosed and mismatched tags are tolerated.
+#[must_use]
+pub fn parse(html: &str) -> Vec<Node> {
+    // the last element of the stack collects the top-level nodes.
+    let mut stack: Vec<Element> = vec![Element {
+        name: String::new(),
+        attrs: vec![],
+        children: vec![],
+    }];
+
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(close) = rest.strip_prefix("</") {
+            let end = close.find('>').unwrap_or(close.len());
+            let name = close[..end].trim().to_ascii_lowercase();
+            rest = close.get(end + 1..).unwrap_or("");
+            close_element(&mut stack, &name);
+        } else if let Some((element, self_closing, remainder)) = parse_open_tag(rest) {
+            rest = remainder;
+
+            if RAW_TEXT_ELEMENTS.contains(&element.name.as_str()) {
+                let closing = format!("</{}", element.name);
+                let end = rest
+                    .to_ascii_lowercase()
+                    .find(&closing)
+                    .unwrap_or(rest.len());
+                rest = &rest[end..];
+                // raw text is dropped, it is never part of a puzzle description.
+                stack.push(element);
+            } else if self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+                append(&mut stack, Node::Element(element));
+            } else {
+                stack.push(element);
+            }
+        } else {
+            // a lone `<` that does not start a tag.
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+        }
+    }
+
+    while stack.len() > 1 {
+        let element = stack.pop().unwrap();
+        append(&mut stack, Node::Element(element));
+    }
+
+    stack.pop().unwrap().children
+}
+
+fn push_text(stack: &mut [Element], text: &str) {
+    if !text.is_empty() {
+        append(stack, Node::Text(decode_entities(text)));
+    }
+}
+
+fn append(stack: &mut [Element], node: Node) {
+    stack.last_mut().unwrap().children.push(node);
+}
+
+fn close_element(stack: &mut Vec<Element>, name: &str) {
+    // ignore stray closing tags that have no open counterpart.
+    if !stack.iter().skip(1).any(|e| e.name == name) {
+        return;
+    }
+
+    while stack.len() > 1 {
+        let element = stack.pop().unwrap();
+        let is_match = element.name == name;
+        append(stack, Node::Element(element));
+        if is_match {
+            break;
+        }
+    }
+}
+
+/// Parses `<name attr="value" ...>`, returning the element, whether it is self-closing and the remaining input.
+fn parse_open_tag(s: &str) -> Option<(Element, bool, &str)> {
+    let body = &s[1..];
+    let name_len = body
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(body.len());
+
+    if name_len == 0 || !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let name = body[..name_len].to_ascii_lowercase();
+    let mut rest = &body[name_len..];
+    let mut attrs = vec![];
+
+    loop {
+        rest = rest.trim_start();
+
+        if let Some(r) = rest.strip_prefix("/>") {
+            return Some((element(name, attrs), true, r));
+        }
+        if let Some(r) = rest.strip_prefix('>') {
+            return Some((element(name, attrs), false, r));
+        }
+        if let Some(r) = rest.strip_prefix('/') {
+            rest = r;
+            continue;
+        }
+        if rest.is_empty() {
+            return Some((element(name, attrs), false, rest));
+        }
+
+        let key_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let key = rest[..key_len].to_ascii_lowercase();
+        rest = rest[key_len..].trim_start();
+
+        let value = if let Some(r) = rest.strip_prefix('=') {
+            let r = r.trim_start();
+            if let Some(quote) = r.chars().next().filter(|c| *c == '"' || *c == '\'') {
+                let end = r[1..].find(quote).map_or(r.len(), |end| end + 1);
+                rest = r.get(end + 1..).unwrap_or("");
+                decode_entities(&r[1..end])
+            } else {
+                let end = r
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(r.len());
+                rest = &r[end..];
+                decode_entities(&r[..end])
+            }
+        } else {
+            String::new()
+        };
+
+        attrs.push((key, value));
+    }
+}
+
+fn element(name: String, attrs: Vec<(String, String)>) -> Element {
+    Element {
+        name,
+        attrs,
+        children: vec![],
+    }
+}
+
+/// Decodes the named entities that occur on Advent of Code pages as well as numeric entities.
+#[must_use]
+pub fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the puzzle description of a day page to markdown.
+/// Only the `<article>` elements and the "Your puzzle answer was" paragraphs are kept.
+#[must_use]
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let nodes = parse(html);
+    let root = find_all(&nodes, "main")
+        .first()
+        .map_or(nodes.as_slice(), |main| main.children.as_slice());
+
+    let mut blocks = vec![];
+
+    for node in root {
+        let Node::Element(e) = node else {
+            continue;
+        };
+
+        if e.name == "article" {
+            render_blocks(&e.children, &mut blocks);
+        } else if e.name == "p" && e.text().starts_with("Your puzzle answer was") {
+            blocks.push(render_inline(&e.children));
+        }
+    }
+
+    let mut md = blocks.join("\n\n");
+    md.push('\n');
+    md
+}
+
+fn render_blocks(nodes: &[Node], blocks: &mut Vec<String>) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => {
+                let t = collapse_whitespace(t);
+                if !t.trim().is_empty() {
+                    blocks.push(t.trim().to_string());
+                }
+            }
+            Node::Element(e) => match e.name.as_str() {
+                "h1" | "h2" | "h3" => {
+                    let level = "#".repeat(usize::from(e.name.as_bytes()[1] - b'0'));
+                    blocks.push(format!("{level} {}", render_inline(&e.children).trim()));
+                }
+                "p" => blocks.push(render_inline(&e.children).trim().to_string()),
+                "pre" => {
+                    let code = e.text();
+                    blocks.push(format!("```\n{}\n```", code.trim_end_matches('\n')));
+                }
+                "ul" | "ol" => {
+                    let mut items = String::new();
+                    for (i, li) in find_all(&e.children, "li").iter().enumerate() {
+                        let bullet = if e.name == "ol" {
+                            format!("{}.", i + 1)
+                        } else {
+                            "-".into()
+                        };
+                        let _ = writeln!(items, "{bullet} {}", render_inline(&li.children).trim());
+                    }
+                    blocks.push(items.trim_end().to_string());
+                }
+                "script" | "style" => {}
+                _ => render_blocks(&e.children, blocks),
+            },
+        }
+    }
+}
+
+fn render_inline(nodes: &[Node]) -> String {
+    let mut s = String::new();
+
+    for node in nodes {
+        match node {
+            Node::Text(t) => s.push_str(&collapse_whitespace(t)),
+            Node::Element(e) => match e.name.as_str() {
+                "code" => {
+                    // example answers are marked up as `<code><em>42</em></code>`.
+                    let is_emphasized = matches!(
+                        e.children.as_slice(),
+                        [Node::Element(inner)] if inner.name == "em"
+                    );
+                    if is_emphasized {
+                        let _ = write!(s, "*`{}`*", e.text());
+                    } else {
+                        let _ = write!(s, "`{}`", e.text());
+                    }
+                }
+                "em" | "i" => {
+                    let _ = write!(s, "*{}*", render_inline(&e.children));
+                }
+                "strong" | "b" => {
+                    let _ = write!(s, "**{}**", render_inline(&e.children));
+                }
+                "a" => {
+                    let text = render_inline(&e.children);
+                    match e.attr("href") {
+                        Some(href) => {
+                            let _ = write!(s, "[{text}]({href})");
+                        }
+                        None => s.push_str(&text),
+                    }
+                }
+                "br" => s.push('\n'),
+                _ => s.push_str(&render_inline(&e.children)),
+            },
+        }
+    }
+
+    s
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut last_was_space = false;
+
+    for c in s.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            out.push(c);
+            last_was_space = false;
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, find_all, parse, puzzle_to_markdown, Node};
+
+    #[test]
+    fn parses_nested_elements() {
+        let nodes = parse(r#"<p class="x">a <em>b</em><br>c</p>"#);
+        let p = find_all(&nodes, "p")[0];
+        assert_eq!(p.attr("class"), Some("x"));
+        assert_eq!(p.text(), "a bc");
+        assert_eq!(p.children.len(), 4);
+    }
+
+    #[test]
+    fn tolerates_unclosed_and_stray_tags() {
+        let nodes = parse("<div><p>one<p>two</span></div>");
+        assert_eq!(find_all(&nodes, "div")[0].text(), "onetwo");
+    }
+
+    #[test]
+    fn skips_comments_doctype_and_scripts() {
+        let nodes = parse("<!DOCTYPE html><!-- <p>no</p> --><script>if (a < b) {}</script>yes");
+        let text: Vec<_> = nodes
+            .iter()
+            .filter_map(|n| match n {
+                Node::Text(t) => Some(t.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect();
+        assert_eq!(text, vec!["yes"]);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &#39;c&#x27; & d"),
+            "a <b> & 'c' & d"
+        );
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>Something is <em>wrong</em> with
+<a href="/2023/about">global snow production</a>.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul><li>one</li><li><code>two</code></li></ul>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54304</code>.</p>
+<p>You can also <a href="/2023/auth">[Share]</a> this puzzle.</p>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "Something is *wrong* with [global snow production](/2023/about).",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "- one",
+            "- `two`",
+            "",
+            "Adding these together produces *`142`*.",
+            "",
+            "Your puzzle answer was `54304`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(puzzle_to_markdown(html), expected);
+    }
+}
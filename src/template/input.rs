@@ -0,0 +1,322 @@
+/// Selects the input a solution runs against.
+///
+/// By default, solutions read their puzzle input from `data/{year}/inputs`. The flags
+/// `--example [n]` and `--input <path>` select an example file or an arbitrary file instead,
+/// `--input -` reads from stdin. `solve` passes these flags on to the solution binary.
+///
+/// Inputs are normalised when they are read: line endings become `\n`, a byte order mark is removed
+/// and the input ends with exactly one newline. Empty inputs, like the placeholder `scaffold` creates,
+/// and HTML pages saved as input are rejected with a hint on how to get the real input.
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use crate::template::get_data_path;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The file does not exist.
+    Missing,
+    /// The input is empty or only whitespace.
+    Empty,
+    /// The input is an HTML page, e.g. the error page of a download without a valid session.
+    Html,
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => Error::Missing,
+            _ => Error::IO(e),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Missing => write!(f, "the file does not exist"),
+            Error::Empty => write!(f, "the input is empty"),
+            Error::Html => write!(
+                f,
+                "the input is an HTML page, it was probably downloaded without a valid session cookie"
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Normalises line endings, removes a byte order mark and ends the input with exactly one newline.
+/// Fails if the input is empty or an HTML page.
+pub fn normalize(input: &str) -> Result<String, Error> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    if input.trim().is_empty() {
+        return Err(Error::Empty);
+    }
+
+    let start: String = input.trim_start().chars().take(15).collect();
+    let start = start.to_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Err(Error::Html);
+    }
+
+    let mut input = input.replace("\r\n", "\n");
+    input.truncate(input.trim_end_matches('\n').len());
+    input.push('\n');
+    Ok(input)
+}
+
+/// Reads and normalises an input file.
+pub fn read_path(path: impl AsRef<Path>) -> Result<String, Error> {
+    normalize(&fs::read_to_string(path)?)
+}
+
+/// What to run to get a missing input of a folder of the data directory.
+#[must_use]
+pub fn hint(folder: &str, day: Day, error: &Error) -> Option<String> {
+    match (folder, error) {
+        (_, Error::IO(_)) => None,
+        ("inputs", _) => Some(format!("Run `cargo download {day}` to download it.")),
+        ("examples", Error::Missing | Error::Empty) => Some(format!(
+            "Run `cargo examples {day}` to extract it from the puzzle description."
+        )),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Source {
+    /// The puzzle input, `data/{year}/inputs/{day}.txt`.
+    #[default]
+    Puzzle,
+    /// An example, `data/{year}/examples/{day}.txt` or `{day}-{n}.txt`.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// A file path, where `-` stands for stdin.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        if path == "-" {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(path))
+        }
+    }
+
+    /// Parses the input flags from command-line arguments.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let example = args.iter().position(|x| x == "--example");
+        let input = args.iter().position(|x| x == "--input");
+
+        match (example, input) {
+            (Some(_), Some(_)) => Err("`--example` and `--input` cannot be combined".into()),
+            (Some(i), None) => Ok(Source::Example(
+                args.get(i + 1).and_then(|n| n.parse().ok()),
+            )),
+            (None, Some(i)) => match args.get(i + 1) {
+                Some(path) => Ok(Source::from_path(path)),
+                None => Err("`--input` expects a path, or `-` for stdin".into()),
+            },
+            (None, None) => Ok(Source::Puzzle),
+        }
+    }
+
+    /// Reads the input flags from the arguments of this process. Exits if they are malformed.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Source::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("Error: {e}.");
+            process::exit(1);
+        })
+    }
+
+    /// The flags that select this input, to pass on to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Source::Puzzle => vec![],
+            Source::Example(None) => vec!["--example".into()],
+            Source::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Source::File(path) => vec!["--input".into(), path.display().to_string()],
+            Source::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == Source::Puzzle
+    }
+
+    /// Reads and normalises the input, see [`normalize`].
+    pub fn read(&self, year: Year, day: Day) -> Result<String, Error> {
+        match self.path(year, day) {
+            Some(path) => read_path(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                normalize(&input)
+            }
+        }
+    }
+
+    fn path(&self, year: Year, day: Day) -> Option<PathBuf> {
+        let path = match self {
+            Source::Puzzle => get_data_path("inputs", year, &format!("{day}.txt")),
+            Source::Example(None) => get_data_path("examples", year, &format!("{day}.txt")),
+            Source::Example(Some(n)) => get_data_path("examples", year, &format!("{day}-{n}.txt")),
+            Source::File(path) => return Some(path.clone()),
+            Source::Stdin => return None,
+        };
+        Some(PathBuf::from(path))
+    }
+
+    /// Reads the input, exiting with an error message if that fails.
+    #[must_use]
+    pub fn read_or_exit(&self, year: Year, day: Day) -> String {
+        self.read(year, day).unwrap_or_else(|e| {
+            eprintln!(
+                "Could not read input from {}: {}",
+                self.describe(year, day),
+                self.explain(day, &e)
+            );
+            process::exit(1);
+        })
+    }
+
+    /// Why this input cannot be read and what to run to get it, e.g.
+    /// ``the file does not exist. Run `cargo download 01` to download it.``
+    #[must_use]
+    pub fn explain(&self, day: Day, error: &Error) -> String {
+        let folder = match self {
+            Source::Puzzle => "inputs",
+            Source::Example(_) => "examples",
+            Source::File(_) | Source::Stdin => return format!("{error}."),
+        };
+
+        match hint(folder, day, error) {
+            Some(hint) => format!("{error}. {hint}"),
+            None => format!("{error}."),
+        }
+    }
+
+    /// Where the input comes from, e.g. `data/2023/examples/01-2.txt` or `stdin`.
+    #[must_use]
+    pub fn describe(&self, year: Year, day: Day) -> String {
+        self.path(year, day)
+            .map_or_else(|| self.to_string(), |path| path.display().to_string())
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Puzzle => write!(f, "puzzle input"),
+            Source::Example(None) => write!(f, "example"),
+            Source::Example(Some(n)) => write!(f, "example {n}"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{hint, normalize, Error, Source};
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_args() {
+        assert_eq!(Source::from_args(&args("2023-01")), Ok(Source::Puzzle));
+        assert_eq!(
+            Source::from_args(&args("2023-01 --example --time")),
+            Ok(Source::Example(None))
+        );
+        assert_eq!(
+            Source::from_args(&args("2023-01 --example 2")),
+            Ok(Source::Example(Some(2)))
+        );
+        assert_eq!(
+            Source::from_args(&args("2023-01 --input other.txt")),
+            Ok(Source::File(PathBuf::from("other.txt")))
+        );
+        assert_eq!(
+            Source::from_args(&args("2023-01 --input -")),
+            Ok(Source::Stdin)
+        );
+        assert!(Source::from_args(&args("2023-01 --input")).is_err());
+        assert!(Source::from_args(&args("2023-01 --input a --example")).is_err());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for source in [
+            Source::Puzzle,
+            Source::Example(None),
+            Source::Example(Some(1)),
+            Source::File(PathBuf::from("a.txt")),
+            Source::Stdin,
+        ] {
+            let mut args = vec!["2023-01".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(Source::from_args(&args), Ok(source));
+        }
+    }
+
+    #[test]
+    fn describes_paths() {
+        assert_eq!(
+            Source::Example(Some(2)).describe(year!(2023), day!(1)),
+            "data/2023/examples/01-2.txt"
+        );
+        assert_eq!(Source::Stdin.describe(year!(2023), day!(1)), "stdin");
+    }
+
+    #[test]
+    fn normalizes_inputs() {
+        assert_eq!(normalize("a\r\nb\r\n").unwrap(), "a\nb\n");
+        assert_eq!(normalize("\u{feff}a\nb").unwrap(), "a\nb\n");
+        assert_eq!(normalize("a\n\nb\n\n\n").unwrap(), "a\n\nb\n");
+        assert_eq!(normalize("  a\n").unwrap(), "  a\n");
+    }
+
+    #[test]
+    fn rejects_placeholders_and_html() {
+        assert!(matches!(normalize(""), Err(Error::Empty)));
+        assert!(matches!(normalize("\u{feff}\n \n"), Err(Error::Empty)));
+        assert!(matches!(
+            normalize("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(Error::Html)
+        ));
+        assert!(matches!(normalize("\n<html>"), Err(Error::Html)));
+        assert!(normalize("<>^v\n").is_ok());
+    }
+
+    #[test]
+    fn suggests_commands() {
+        assert_eq!(
+            Source::Puzzle.explain(day!(1), &Error::Empty),
+            "the input is empty. Run `cargo download 01` to download it."
+        );
+        assert_eq!(
+            hint("examples", day!(1), &Error::Missing).as_deref(),
+            Some("Run `cargo examples 01` to extract it from the puzzle description.")
+        );
+        assert_eq!(
+            Source::Stdin.explain(day!(1), &Error::Empty),
+            "the input is empty."
+        );
+    }
+}
@@ -0,0 +1,438 @@
+/// Ledger of answers submitted to the website and the verdicts they received.
+///
+/// Submissions of a day are appended to `data/{year}/submissions/{day}.jsonl`, one JSON object per line.
+/// Before submitting, the ledger is consulted so that an answer which is known to be wrong - or
+/// which lies outside the bounds established by earlier "too high" / "too low" verdicts - is not sent again.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SubmissionOutcome;
+use crate::template::get_data_path;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed submission ledger: {e}"),
+            Error::IO(e) => write!(f, "could not access submission ledger: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    RateLimited,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the verdict means the answer was checked and found wrong.
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "already_solved" => Ok(Verdict::AlreadySolved),
+            "unknown" => Ok(Verdict::Unknown),
+            x => Err(Error::Parser(format!("unknown verdict \"{x}\""))),
+        }
+    }
+}
+
+/// One submitted answer. Timestamps are seconds since the unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub submitted_at: u64,
+    /// Until when the website refuses further submissions, if it asked us to wait.
+    pub cooldown_until: Option<u64>,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn new(part: u8, answer: &str, outcome: &SubmissionOutcome, submitted_at: u64) -> Self {
+        let (verdict, wait) = match outcome {
+            SubmissionOutcome::Correct => (Verdict::Correct, None),
+            SubmissionOutcome::TooHigh { wait } => (Verdict::TooHigh, *wait),
+            SubmissionOutcome::TooLow { wait } => (Verdict::TooLow, *wait),
+            SubmissionOutcome::Incorrect { wait } => (Verdict::Incorrect, *wait),
+            SubmissionOutcome::RateLimited { wait } => (Verdict::RateLimited, Some(*wait)),
+            SubmissionOutcome::AlreadySolved => (Verdict::AlreadySolved, None),
+            SubmissionOutcome::Unknown(_) => (Verdict::Unknown, None),
+        };
+
+        Self {
+            part,
+            answer: answer.trim().to_string(),
+            verdict,
+            submitted_at,
+            cooldown_until: wait.map(|wait| submitted_at + wait.as_secs()),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        let mut obj: HashMap<String, JsonValue> = HashMap::new();
+        obj.insert("part".into(), f64::from(self.part).into());
+        obj.insert("answer".into(), self.answer.clone().into());
+        obj.insert("verdict".into(), self.verdict.as_str().to_string().into());
+        #[allow(clippy::cast_precision_loss)]
+        obj.insert("submitted_at".into(), (self.submitted_at as f64).into());
+        #[allow(clippy::cast_precision_loss)]
+        obj.insert(
+            "cooldown_until".into(),
+            self.cooldown_until
+                .map_or(JsonValue::Null, |t| (t as f64).into()),
+        );
+        obj.into()
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_json(value: &JsonValue) -> Result<Self, Error> {
+        let obj: &HashMap<String, JsonValue> = value
+            .get()
+            .ok_or_else(|| Error::Parser("expected an object".into()))?;
+
+        let number = |key: &str| {
+            obj.get(key)
+                .and_then(JsonValue::get::<f64>)
+                .map(|x| *x as u64)
+                .ok_or_else(|| Error::Parser(format!("missing numeric field \"{key}\"")))
+        };
+
+        let string = |key: &str| {
+            obj.get(key)
+                .and_then(JsonValue::get::<String>)
+                .ok_or_else(|| Error::Parser(format!("missing field \"{key}\"")))
+        };
+
+        let cooldown_until = match obj.get("cooldown_until") {
+            Some(JsonValue::Null) | None => None,
+            Some(_) => Some(number("cooldown_until")?),
+        };
+
+        Ok(Self {
+            part: number("part")? as u8,
+            answer: string("answer")?.clone(),
+            verdict: string("verdict")?.parse()?,
+            submitted_at: number("submitted_at")?,
+            cooldown_until,
+        })
+    }
+}
+
+/// Why a submission was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blocked {
+    AlreadySolved { answer: String },
+    KnownWrong { verdict: Verdict },
+    AboveBound { bound: String },
+    BelowBound { bound: String },
+    Cooldown { remaining: Duration },
+}
+
+impl Display for Blocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blocked::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Blocked::KnownWrong { verdict } => match verdict {
+                Verdict::TooHigh => write!(f, "this answer was already submitted and is too high."),
+                Verdict::TooLow => write!(f, "this answer was already submitted and is too low."),
+                _ => write!(f, "this answer was already submitted and is wrong."),
+            },
+            Blocked::AboveBound { bound } => write!(
+                f,
+                "this answer is too high, {bound} was already rejected as too high."
+            ),
+            Blocked::BelowBound { bound } => write!(
+                f,
+                "this answer is too low, {bound} was already rejected as too low."
+            ),
+            Blocked::Cooldown { remaining } => write!(
+                f,
+                "the website asked to wait before submitting again, {}s remaining.",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Ledger {
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Checks whether `answer` may be submitted for `part` at time `now`.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Blocked> {
+        let answer = answer.trim();
+
+        // cooldowns apply to the whole day, regardless of the part.
+        if let Some(until) = self
+            .submissions
+            .iter()
+            .filter_map(|s| s.cooldown_until)
+            .max()
+            .filter(|until| *until > now)
+        {
+            return Err(Blocked::Cooldown {
+                remaining: Duration::from_secs(until - now),
+            });
+        }
+
+        let submissions = self.submissions.iter().filter(|s| s.part == part);
+
+        for s in submissions.clone() {
+            if s.verdict == Verdict::Correct {
+                return Err(Blocked::AlreadySolved {
+                    answer: s.answer.clone(),
+                });
+            }
+            if s.answer == answer && s.verdict.is_wrong() {
+                return Err(Blocked::KnownWrong { verdict: s.verdict });
+            }
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric = |verdict: Verdict| {
+            submissions
+                .clone()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, s)))
+        };
+
+        if let Some((_, s)) = numeric(Verdict::TooHigh)
+            .filter(|(bound, _)| value >= *bound)
+            .min_by_key(|(bound, _)| *bound)
+        {
+            return Err(Blocked::AboveBound {
+                bound: s.answer.clone(),
+            });
+        }
+
+        if let Some((_, s)) = numeric(Verdict::TooLow)
+            .filter(|(bound, _)| value <= *bound)
+            .max_by_key(|(bound, _)| *bound)
+        {
+            return Err(Blocked::BelowBound {
+                bound: s.answer.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Path of the ledger of a day. E.g. like `data/2023/submissions/01.jsonl`.
+#[must_use]
+pub fn get_path(year: Year, day: Day) -> String {
+    get_data_path("submissions", year, &format!("{day}.jsonl"))
+}
+
+/// Reads the ledger of a day. A missing ledger is treated as empty.
+pub fn read(year: Year, day: Day) -> Result<Ledger, Error> {
+    match fs::read_to_string(get_path(year, day)) {
+        Ok(contents) => parse(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Appends a submission to the ledger of a day.
+pub fn append(year: Year, day: Day, submission: &Submission) -> Result<(), Error> {
+    let path = get_path(year, day);
+
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let line = submission
+        .to_json()
+        .stringify()
+        .map_err(|e| Error::Parser(e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+fn parse(contents: &str) -> Result<Ledger, Error> {
+    let submissions = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let value: JsonValue = line.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+            Submission::from_json(&value)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Ledger { submissions })
+}
+
+/// Current time in seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse, Blocked, Ledger, Submission, Verdict};
+    use crate::template::aoc_client::SubmissionOutcome;
+
+    fn ledger(entries: &[(u8, &str, SubmissionOutcome)]) -> Ledger {
+        Ledger {
+            submissions: entries
+                .iter()
+                .map(|(part, answer, outcome)| Submission::new(*part, answer, outcome, 1000))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let submission = Submission::new(
+            2,
+            "42\n",
+            &SubmissionOutcome::TooLow {
+                wait: Some(Duration::from_secs(60)),
+            },
+            1000,
+        );
+        let line = submission.to_json().stringify().unwrap();
+        let parsed = parse(&format!("{line}\n\n")).unwrap();
+
+        assert_eq!(parsed.submissions, vec![submission]);
+        assert_eq!(parsed.submissions[0].answer, "42");
+        assert_eq!(parsed.submissions[0].cooldown_until, Some(1060));
+    }
+
+    #[test]
+    fn blocks_known_wrong_answers() {
+        let ledger = ledger(&[(1, "abc", SubmissionOutcome::Incorrect { wait: None })]);
+        assert_eq!(
+            ledger.check(1, "abc", 2000),
+            Err(Blocked::KnownWrong {
+                verdict: Verdict::Incorrect
+            })
+        );
+        assert_eq!(ledger.check(1, "abd", 2000), Ok(()));
+        assert_eq!(ledger.check(2, "abc", 2000), Ok(()));
+    }
+
+    #[test]
+    fn blocks_answers_outside_bounds() {
+        let ledger = ledger(&[
+            (1, "100", SubmissionOutcome::TooHigh { wait: None }),
+            (1, "80", SubmissionOutcome::TooHigh { wait: None }),
+            (1, "20", SubmissionOutcome::TooLow { wait: None }),
+        ]);
+
+        assert_eq!(
+            ledger.check(1, "90", 2000),
+            Err(Blocked::AboveBound { bound: "80".into() })
+        );
+        assert_eq!(
+            ledger.check(1, "20", 2000),
+            Err(Blocked::KnownWrong {
+                verdict: Verdict::TooLow
+            })
+        );
+        assert_eq!(
+            ledger.check(1, "5", 2000),
+            Err(Blocked::BelowBound { bound: "20".into() })
+        );
+        assert_eq!(ledger.check(1, "50", 2000), Ok(()));
+        assert_eq!(ledger.check(1, "x", 2000), Ok(()));
+    }
+
+    #[test]
+    fn blocks_during_cooldown() {
+        let ledger = ledger(&[(
+            2,
+            "7",
+            SubmissionOutcome::RateLimited {
+                wait: Duration::from_secs(30),
+            },
+        )]);
+
+        assert_eq!(
+            ledger.check(1, "1", 1010),
+            Err(Blocked::Cooldown {
+                remaining: Duration::from_secs(20)
+            })
+        );
+        assert_eq!(ledger.check(1, "1", 1030), Ok(()));
+    }
+
+    #[test]
+    fn blocks_solved_parts() {
+        let ledger = ledger(&[(1, "7", SubmissionOutcome::Correct)]);
+        assert_eq!(
+            ledger.check(1, "8", 2000),
+            Err(Blocked::AlreadySolved { answer: "7".into() })
+        );
+        assert_eq!(ledger.check(2, "8", 2000), Ok(()));
+    }
+}
@@ -0,0 +1,133 @@
+/// Resource limits that `all` imposes on the solutions it runs in child processes.
+///
+/// The limits are handed to the solution binary in environment variables and enforced by the runner:
+/// a watchdog thread ends the process once a part or the whole day runs past its timeout, after
+/// recording the running part as timed out. The memory ceiling caps the address space of the process,
+/// so an allocation beyond it aborts the solution.
+use std::env;
+use std::process::{Command, ExitStatus};
+use std::time::Duration;
+
+/// Wall-clock timeout of a whole day, in seconds.
+pub const DAY_TIMEOUT_ENV: &str = "AOC_DAY_TIMEOUT";
+/// Wall-clock timeout of each part, in seconds.
+pub const PART_TIMEOUT_ENV: &str = "AOC_PART_TIMEOUT";
+/// Memory ceiling, in megabytes.
+pub const MEMORY_LIMIT_ENV: &str = "AOC_MEMORY_LIMIT";
+/// First part to run. Earlier parts are left out silently, as they already ran in an earlier process.
+pub const RESUME_PART_ENV: &str = "AOC_RESUME_PART";
+
+/// Exit code of a solution that ran out of time, the same that GNU `timeout` uses.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Limits {
+    pub day_timeout: Option<Duration>,
+    pub part_timeout: Option<Duration>,
+    pub memory_mb: Option<u64>,
+}
+
+impl Limits {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Limits::default()
+    }
+
+    /// Reads the limits handed to this process by [`Limits::apply`].
+    #[must_use]
+    pub fn from_env() -> Self {
+        let seconds = |key: &str| {
+            env::var(key)
+                .ok()
+                .and_then(|s| s.parse::<f64>().ok())
+                .map(Duration::from_secs_f64)
+        };
+
+        Self {
+            day_timeout: seconds(DAY_TIMEOUT_ENV),
+            part_timeout: seconds(PART_TIMEOUT_ENV),
+            memory_mb: env::var(MEMORY_LIMIT_ENV).ok().and_then(|s| s.parse().ok()),
+        }
+    }
+
+    /// Hands the limits to a solution binary.
+    pub fn apply(&self, cmd: &mut Command) {
+        if let Some(timeout) = self.day_timeout {
+            cmd.env(DAY_TIMEOUT_ENV, timeout.as_secs_f64().to_string());
+        }
+        if let Some(timeout) = self.part_timeout {
+            cmd.env(PART_TIMEOUT_ENV, timeout.as_secs_f64().to_string());
+        }
+        if let Some(memory_mb) = self.memory_mb {
+            cmd.env(MEMORY_LIMIT_ENV, memory_mb.to_string());
+        }
+    }
+}
+
+/// Caps the address space of this process.
+#[cfg(unix)]
+pub fn limit_memory(memory_mb: u64) {
+    let bytes = memory_mb.saturating_mul(1024 * 1024);
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+
+    // SAFETY: `setrlimit` only reads the passed struct.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        eprintln!(
+            "Failed to set memory limit: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+#[cfg(not(unix))]
+pub fn limit_memory(_memory_mb: u64) {
+    eprintln!("Memory limits are only supported on unix systems.");
+}
+
+/// Whether a solution process was ended by a signal, e.g. because an allocation beyond the memory
+/// ceiling aborted it or the system ran out of memory.
+#[cfg(unix)]
+#[must_use]
+pub fn was_killed(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal().is_some()
+}
+
+#[cfg(not(unix))]
+#[must_use]
+pub fn was_killed(_status: &ExitStatus) -> bool {
+    false
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Limits, DAY_TIMEOUT_ENV, MEMORY_LIMIT_ENV, PART_TIMEOUT_ENV};
+    use std::process::Command;
+    use std::time::Duration;
+
+    #[test]
+    fn hands_limits_to_commands() {
+        let limits = Limits {
+            day_timeout: Some(Duration::from_secs(30)),
+            part_timeout: Some(Duration::from_millis(2500)),
+            memory_mb: None,
+        };
+
+        let mut cmd = Command::new("true");
+        limits.apply(&mut cmd);
+
+        let envs: Vec<_> = cmd
+            .get_envs()
+            .map(|(k, v)| (k.to_str().unwrap(), v.unwrap().to_str().unwrap()))
+            .collect();
+
+        assert!(envs.contains(&(DAY_TIMEOUT_ENV, "30")));
+        assert!(envs.contains(&(PART_TIMEOUT_ENV, "2.5")));
+        assert!(!envs.iter().any(|(k, _)| *k == MEMORY_LIMIT_ENV));
+        assert!(!limits.is_empty());
+        assert!(Limits::default().is_empty());
+    }
+}
@@ -0,0 +1,400 @@
+/// Renders stored puzzle descriptions in the terminal.
+///
+/// Handles the markdown that [`puzzle_to_markdown`](crate::template::html::puzzle_to_markdown) writes:
+/// headings, paragraphs with emphasis, code spans and links, lists and code blocks. Example answers,
+/// emphasised code like `` *`142`* ``, are highlighted. Text is wrapped to the given width, code
+/// blocks are kept as they are.
+use crate::template::html;
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RESET, ANSI_YELLOW};
+
+/// Widest column text is wrapped to, wider lines are hard to read.
+pub const MAX_WIDTH: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Code,
+    /// Emphasised code, the way example answers are marked up.
+    Answer,
+    Emphasis,
+}
+
+impl Style {
+    fn ansi(self) -> Option<String> {
+        match self {
+            Style::Plain => None,
+            Style::Code => Some(ANSI_GREEN.into()),
+            Style::Answer => Some(format!("{ANSI_BOLD}{ANSI_YELLOW}")),
+            Style::Emphasis => Some(ANSI_BOLD.into()),
+        }
+    }
+}
+
+/// A run of text without spaces in a single style.
+type Piece = (String, Style);
+
+/// Renders markdown for a terminal that is `width` columns wide.
+#[must_use]
+pub fn render(markdown: &str, width: usize) -> String {
+    let width = width.clamp(20, MAX_WIDTH);
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut lines = markdown.lines();
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            out.extend(render_paragraph(&paragraph.join("\n"), width));
+            out.push(String::new());
+            paragraph.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut out);
+        } else if trimmed.starts_with('#') {
+            flush(&mut paragraph, &mut out);
+            let title = trimmed.trim_start_matches('#').trim();
+            out.push(format!("{ANSI_BOLD}{ANSI_GREEN}{title}{ANSI_RESET}"));
+            out.push(String::new());
+        } else if trimmed.starts_with("```") {
+            flush(&mut paragraph, &mut out);
+            let block: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .collect();
+            out.extend(render_code_block(&block));
+            out.push(String::new());
+        } else if let Some(start) = trimmed.find("<pre>") {
+            // descriptions stored by other tools keep code blocks as html.
+            flush(&mut paragraph, &mut out);
+            let mut block = trimmed[start..].to_string();
+            while !block.contains("</pre>") {
+                let Some(line) = lines.next() else {
+                    break;
+                };
+                block.push('\n');
+                block.push_str(line);
+            }
+            let nodes = html::parse(&block);
+            if let Some(pre) = html::find_all(&nodes, "pre").first() {
+                let text = pre.text();
+                out.extend(render_code_block(
+                    &text.trim_end_matches('\n').lines().collect::<Vec<_>>(),
+                ));
+                out.push(String::new());
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    flush(&mut paragraph, &mut out);
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    let mut s = out.join("\n");
+    s.push('\n');
+    s
+}
+
+fn is_list_item(line: &str) -> bool {
+    line.starts_with("- ")
+        || line
+            .split_once(". ")
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn render_code_block(lines: &[&str]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| format!("    {}{line}{ANSI_RESET}", Style::Code.ansi().unwrap()))
+        .collect()
+}
+
+/// Renders a paragraph or list, wrapping each list item with a hanging indent.
+fn render_paragraph(text: &str, width: usize) -> Vec<String> {
+    let mut out = vec![];
+    let mut item: Vec<&str> = vec![];
+
+    let mut render_item = |item: &mut Vec<&str>| {
+        if item.is_empty() {
+            return;
+        }
+        // line breaks within a paragraph come from `<br>` and are kept.
+        let text = item.join("\n");
+        let (bullet, rest) = match text.split_once(' ') {
+            Some((bullet, rest)) if is_list_item(&text) => (bullet.to_string(), rest.to_string()),
+            _ => (String::new(), text.clone()),
+        };
+        let indent = if bullet.is_empty() {
+            0
+        } else {
+            bullet.chars().count() + 1
+        };
+
+        for (i, line) in wrap(&parse_inline(&rest), width.saturating_sub(indent).max(1))
+            .into_iter()
+            .enumerate()
+        {
+            let prefix = if i == 0 && indent > 0 {
+                format!("{bullet} ")
+            } else {
+                " ".repeat(indent)
+            };
+            out.push(format!("{prefix}{line}"));
+        }
+        item.clear();
+    };
+
+    for line in text.lines() {
+        if is_list_item(line.trim_start()) {
+            render_item(&mut item);
+        }
+        item.push(line.trim());
+    }
+    render_item(&mut item);
+
+    out
+}
+
+/// Splits inline markdown into styled words. Line breaks are kept as `\n` words.
+fn parse_inline(text: &str) -> Vec<Vec<Piece>> {
+    let mut words: Vec<Vec<Piece>> = vec![];
+    let mut word: Vec<Piece> = vec![];
+
+    let push = |s: &str, style: Style, word: &mut Vec<Piece>, words: &mut Vec<Vec<Piece>>| {
+        let mut parts = s.split(' ').peekable();
+        while let Some(part) = parts.next() {
+            if !part.is_empty() {
+                word.push((part.to_string(), style));
+            }
+            // a space ends the word, unless it is the last part.
+            if parts.peek().is_some() && !word.is_empty() {
+                words.push(std::mem::take(word));
+            }
+        }
+    };
+
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (style, content, len) = if let Some(span) = delimited(rest, "*`", "`*") {
+            (Style::Answer, span, span.len() + 4)
+        } else if let Some(span) = delimited(rest, "**", "**") {
+            (Style::Emphasis, span, span.len() + 4)
+        } else if let Some(span) = delimited(rest, "`", "`") {
+            (Style::Code, span, span.len() + 2)
+        } else if let Some(span) = delimited(rest, "*", "*") {
+            (Style::Emphasis, span, span.len() + 2)
+        } else if let Some((text, len)) = link(rest) {
+            (Style::Plain, text, len)
+        } else if let Some(after) = rest.strip_prefix('\n') {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            words.push(vec![("\n".into(), Style::Plain)]);
+            rest = after;
+            continue;
+        } else {
+            let c = rest.chars().next().unwrap();
+            (Style::Plain, &rest[..c.len_utf8()], c.len_utf8())
+        };
+
+        // answers are also marked up as **`42`** or `*42*`, other emphasis around code
+        // spans, like *the `x` value*, is shown as emphasis.
+        let (style, content) = match style {
+            Style::Emphasis | Style::Code if is_wrapped(content, style) => {
+                (Style::Answer, content[1..content.len() - 1].to_string())
+            }
+            Style::Emphasis => (style, content.replace('`', "")),
+            _ => (style, content.to_string()),
+        };
+        push(&content, style, &mut word, &mut words);
+        rest = &rest[len..];
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Whether emphasis is wrapped around a single code span, or a code span around emphasis.
+fn is_wrapped(content: &str, style: Style) -> bool {
+    let delimiter = if style == Style::Code { '*' } else { '`' };
+    content.len() > 2
+        && content.starts_with(delimiter)
+        && content.ends_with(delimiter)
+        && !content[1..content.len() - 1].contains(delimiter)
+}
+
+/// The text between `open` and `close` at the start of `s`.
+fn delimited<'a>(s: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let rest = s.strip_prefix(open)?;
+    let end = rest.find(close)?;
+    let span = &rest[..end];
+    (!span.is_empty() && !span.contains('\n')).then_some(span)
+}
+
+/// The text of a link like `[text](href)` at the start of `s`, and the length of the link.
+fn link(s: &str) -> Option<(&str, usize)> {
+    let rest = s.strip_prefix('[')?;
+    let end = rest.find("](")?;
+    let href_end = rest[end..].find(')')?;
+    Some((&rest[..end], 1 + end + href_end + 1))
+}
+
+/// Greedily fills lines of `width` columns with words, separated by single spaces.
+fn wrap(words: &[Vec<Piece>], width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in words {
+        if word.len() == 1 && word[0].0 == "\n" {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+            continue;
+        }
+
+        let word_width: usize = word.iter().map(|(s, _)| s.chars().count()).sum();
+
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        for (s, style) in word {
+            match style.ansi() {
+                Some(ansi) => line.push_str(&format!("{ansi}{s}{ANSI_RESET}")),
+                None => line.push_str(s),
+            }
+        }
+        line_width += word_width;
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Width of the terminal, from `COLUMNS` or the terminal itself. Defaults to 80 columns.
+#[must_use]
+pub fn terminal_width() -> usize {
+    if let Some(columns) = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()) {
+        return columns;
+    }
+
+    #[cfg(unix)]
+    {
+        // SAFETY: `ioctl` with `TIOCGWINSZ` only writes the passed struct.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+            && size.ws_col > 0
+        {
+            return usize::from(size.ws_col);
+        }
+    }
+
+    80
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RESET, ANSI_YELLOW};
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| *c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn wraps_paragraphs() {
+        let md =
+            "One two three four five six seven eight nine ten eleven twelve thirteen fourteen.";
+        assert_eq!(
+            strip_ansi(&render(md, 30)),
+            "One two three four five six\nseven eight nine ten eleven\ntwelve thirteen fourteen.\n"
+        );
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let md = "## --- Day 1: Trebuchet?! ---\n\nThe values:\n\n```\n1abc2\npqr3stu8vwx\n```\n\n- first `a`\n- second\n";
+        assert_eq!(
+            strip_ansi(&render(md, 80)),
+            "--- Day 1: Trebuchet?! ---\n\nThe values:\n\n    1abc2\n    pqr3stu8vwx\n\n- first a\n- second\n"
+        );
+    }
+
+    #[test]
+    fn highlights_answers() {
+        let rendered = render(
+            "Adding these produces *`142`*, see [here](/2023/day/1).",
+            80,
+        );
+        assert!(rendered.contains(&format!("{ANSI_BOLD}{ANSI_YELLOW}142{ANSI_RESET},")));
+
+        for md in ["**`142`**", "`*142*`"] {
+            assert_eq!(
+                render(md, 80),
+                format!("{ANSI_BOLD}{ANSI_YELLOW}142{ANSI_RESET}\n")
+            );
+        }
+        assert_eq!(
+            strip_ansi(&rendered),
+            "Adding these produces 142, see here.\n"
+        );
+    }
+
+    #[test]
+    fn styles_code_and_emphasis() {
+        let rendered = render("Use `a b` and *never* **stop** 2*3.", 80);
+        assert!(rendered.contains(&format!(
+            "{ANSI_GREEN}a{ANSI_RESET} {ANSI_GREEN}b{ANSI_RESET}"
+        )));
+        assert!(rendered.contains(&format!("{ANSI_BOLD}never{ANSI_RESET}")));
+        assert_eq!(strip_ansi(&rendered), "Use a b and never stop 2*3.\n");
+    }
+
+    #[test]
+    fn indents_wrapped_list_items() {
+        assert_eq!(
+            strip_ansi(&render("- one two three four\n- five", 20)),
+            "- one two three four\n- five\n"
+        );
+        assert_eq!(
+            strip_ansi(&render("- one two three four five six seven", 20)),
+            "- one two three four\n  five six seven\n"
+        );
+    }
+
+    #[test]
+    fn wraps_items_with_bullets_wider_than_the_line() {
+        let bullet = "1".repeat(24);
+        assert_eq!(
+            strip_ansi(&render(&format!("{bullet}. one two"), 20)),
+            format!("{bullet}. one\n{}two\n", " ".repeat(26))
+        );
+    }
+}
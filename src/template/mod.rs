@@ -1,48 +1,209 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Year};
+use std::env;
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
 pub mod commands;
+pub mod config;
+pub mod example_tests;
+pub mod html;
+pub mod input;
+pub mod ledger;
+pub mod limits;
+pub mod markdown;
+pub mod parse;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod solution;
+pub mod stats;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
-/// Helper function that reads a text file to a string.
+/// Path of a file in the data directory of a year. E.g. like `data/2023/benchmarks.jsonl`.
+/// The data directory is set by `paths.data` of the [config](config).
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn get_year_path(year: Year, file_name: &str) -> String {
+    format!("{}/{year}/{file_name}", config::get().data_dir)
+}
+
+/// Path of a file in a folder of the data directory of a year. E.g. like `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(folder: &str, year: Year, file_name: &str) -> String {
+    get_year_path(year, &format!("{folder}/{file_name}"))
+}
+
+/// Name of the solution binary of a day. E.g. like `2023-01`.
+#[must_use]
+pub fn get_bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
+
+/// Path of the solution binary of a day. E.g. like `./src/bin/2023-01.rs`.
+#[must_use]
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(year, day))
+}
+
+/// Cargo flags that build solution binaries with the features of this binary that affect them.
+/// E.g. like `--features alloc_profile`, so that `cargo run --features alloc_profile -- all` profiles every day.
+#[must_use]
+pub fn get_feature_args() -> Vec<&'static str> {
+    if cfg!(feature = "alloc_profile") {
+        vec!["--features", "alloc_profile"]
+    } else {
+        vec![]
+    }
+}
+
+/// Helper function that reads a text file to a string, normalised like inputs, see [`input::normalize`].
+///
+/// # Panics
+/// Panics with a hint on how to get the file if it is missing, empty or an HTML page.
+#[must_use]
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    read_data_file(folder, year, day, &format!("{day}.txt"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    read_data_file(folder, year, day, &format!("{day}-{part}.txt"))
+}
+
+fn read_data_file(folder: &str, year: Year, day: Day, file_name: &str) -> String {
+    let path = get_data_path(folder, year, file_name);
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+
+    input::read_path(cwd.join(&path)).unwrap_or_else(|e| {
+        let hint = input::hint(folder, day, &e).map_or_else(String::new, |h| format!(" {h}"));
+        panic!("could not read \"{path}\": {e}.{hint}")
+    })
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+/// Also creates the constant `SOLUTION`, the entry of this day in the in-process solution registry.
+///
+/// With two arguments, the parts are the free functions `part_one` and `part_two`. With a third argument,
+/// the parts are those of a type that implements [`Solution`](crate::template::solution::Solution).
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@main $year:expr, $day:expr) => {
+        /// The year of the current puzzle.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// Entry of this day in the solution registry.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                run: run_solution,
+            };
+
         fn main() {
+            use advent_of_code::template::runner;
+            runner::enforce_limits(YEAR, DAY);
+            runner::install_panic_hook();
+            let input = runner::read_input(YEAR, DAY);
+            runner::exit_on_failure(&run_solution(&input));
+        }
+    };
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@main $year, $day);
+
+        fn run_solution(input: &str) -> Vec<advent_of_code::template::report::PartReport> {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            vec![
+                run_part(part_one, input, YEAR, DAY, 1),
+                run_part(part_two, input, YEAR, DAY, 2),
+            ]
+        }
+    };
+    ($year:expr, $day:expr, $solution:ty) => {
+        $crate::solution!(@main $year, $day);
+
+        fn run_solution(input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+            advent_of_code::template::runner::run_solution::<$solution>(input, YEAR, DAY)
+        }
+    };
+}
+
+/// Generates a test for every example of the day that has a sidecar file with the expected answers,
+/// see [`example_tests`](crate::template::example_tests).
+///
+/// Keys of the sidecar other than `part_one` and `part_two` are parameterised runs, which are
+/// declared as `name => run`, where `run` takes the example and returns the answer of the run.
+/// Pass `solution = Type` for a day that implements [`Solution`](crate::template::solution::Solution).
+///
+/// ```ignore
+/// advent_of_code::example_tests!(expansion_10 => |input| Some(solve(input, 10)));
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    (@include $(, $name:ident => $run:expr)*) => {
+        $(
+            fn $name(input: &str) -> Option<String> {
+                advent_of_code::template::example_tests::answer(($run)(input))
+            }
+        )*
+
+        include!(concat!(env!("OUT_DIR"), "/example_tests/", env!("CARGO_BIN_NAME"), ".rs"));
+    };
+    (solution = $solution:ty $(, $name:ident => $run:expr)* $(,)?) => {
+        #[cfg(test)]
+        #[allow(dead_code)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+            use advent_of_code::template::example_tests::answer;
+            use advent_of_code::template::solution::Solution as _;
+
+            fn parse(input: &str) -> <$solution as advent_of_code::template::solution::Solution>::Parsed<'_> {
+                <$solution>::parse(input).unwrap_or_else(|e| panic!("could not parse the example:\n{e}"))
+            }
+
+            fn part_one(input: &str) -> Option<String> {
+                answer(<$solution>::part_one(&parse(input)))
+            }
+
+            fn part_two(input: &str) -> Option<String> {
+                answer(<$solution>::part_two(&parse(input)))
+            }
+
+            $crate::example_tests!(@include $(, $name => $run)*);
+        }
+    };
+    ($($name:ident => $run:expr),* $(,)?) => {
+        #[cfg(test)]
+        #[allow(dead_code)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+            use advent_of_code::template::example_tests::answer;
+
+            fn part_one(input: &str) -> Option<String> {
+                answer(super::part_one(input))
+            }
+
+            fn part_two(input: &str) -> Option<String> {
+                answer(super::part_two(input))
+            }
+
+            $crate::example_tests!(@include $(, $name => $run)*);
         }
     };
 }
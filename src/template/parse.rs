@@ -0,0 +1,194 @@
+/// Errors for malformed puzzle input.
+///
+/// Solution parts may return `Result<Option<T>, E>` instead of `Option<T>`. With [`ParseError`] as `E`,
+/// the runner points at the offending position of the input instead of printing a panic backtrace:
+///
+/// ```text
+/// line 3, column 7: expected a number
+///   3 | seeds: x9 14
+///     |        ^
+/// ```
+use std::fmt::Display;
+
+use nom::error::ErrorKind;
+use nom::IResult;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    /// 1-based line of the error.
+    line: usize,
+    /// 1-based column of the error, in characters.
+    column: usize,
+    /// The line of the input the error is in.
+    snippet: String,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `input`.
+    #[must_use]
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            message: message.into(),
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+
+    /// An error at the start of `at`, which is a slice of `input`, e.g. a line or the input nom has left.
+    #[must_use]
+    pub fn at(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let position = at.as_ptr() as usize;
+
+        let offset = if position >= start && position <= start + input.len() {
+            position - start
+        } else {
+            // not a slice of `input` after all, assume it is what is left of it.
+            input.len().saturating_sub(at.len())
+        };
+
+        Self::at_offset(input, offset, message)
+    }
+
+    /// Converts the error of a nom parser that was run on `input`.
+    #[must_use]
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::at(input, e.input, describe(e.code)),
+            nom::Err::Incomplete(_) => {
+                Self::at_offset(input, input.len(), "unexpected end of input")
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = self.line.to_string();
+        let padding = " ".repeat(gutter.len());
+
+        // tabs are kept, so the caret lines up with the snippet.
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "  {gutter} | {}", self.snippet)?;
+        write!(f, "  {padding} | {indent}^")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs a nom parser on the whole input. Input that is left over, apart from trailing whitespace,
+/// is an error.
+pub fn parse<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    let (rest, output) = parser(input).map_err(|e| ParseError::from_nom(input, e))?;
+
+    if rest.trim().is_empty() {
+        Ok(output)
+    } else {
+        Err(ParseError::at(input, rest, "unexpected input"))
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit | ErrorKind::HexDigit | ErrorKind::OctDigit => "expected a number".into(),
+        ErrorKind::Alpha => "expected a letter".into(),
+        ErrorKind::AlphaNumeric => "expected a letter or number".into(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "expected whitespace".into(),
+        ErrorKind::CrLf => "expected a line break".into(),
+        ErrorKind::Eof | ErrorKind::Tag | ErrorKind::Char | ErrorKind::OneOf => {
+            "unexpected input".into()
+        }
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "invalid value".into(),
+        kind => format!("unexpected input ({})", kind.description().to_lowercase()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, ParseError};
+    use nom::character::complete::{self, newline, space1};
+    use nom::multi::separated_list1;
+    use nom::IResult;
+
+    fn numbers(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
+        separated_list1(newline, separated_list1(space1, complete::u32))(input)
+    }
+
+    #[test]
+    fn parses_whole_input() {
+        assert_eq!(
+            parse("1 2\n3 4\n", numbers),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn points_at_leftover_input() {
+        let err = parse("1 2\n3 x4\n", numbers).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: unexpected input\n  2 | 3 x4\n    |  ^"
+        );
+    }
+
+    #[test]
+    fn converts_nom_errors() {
+        let input = "a";
+        let err = ParseError::from_nom(input, numbers(input).unwrap_err());
+        assert_eq!(err.message(), "expected a number");
+        assert_eq!((err.line(), err.column()), (1, 1));
+    }
+
+    #[test]
+    fn locates_slices() {
+        let input = "ab\ncd\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::at(input, &line[1..], "unknown tile 'd'");
+        assert_eq!((err.line(), err.column()), (2, 2));
+        assert_eq!(err.to_string().lines().nth(1), Some("  2 | cd"));
+    }
+}
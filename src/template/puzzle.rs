@@ -0,0 +1,246 @@
+/// Finds example inputs and example answers in a downloaded puzzle description.
+///
+/// Code blocks are recognised as fenced markdown blocks or as `<pre><code>` elements. Example
+/// answers are the emphasised code spans of a part, like `` *`142`* `` or `<code><em>142</em></code>`.
+use crate::template::html;
+
+/// One part of the puzzle description.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Indices into [`Puzzle::blocks`] of the code blocks in this part.
+    pub blocks: Vec<usize>,
+    /// Emphasised code spans, in order of appearance. The example answer is usually the last one.
+    pub answers: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub blocks: Vec<String>,
+    /// Part one and, once unlocked, part two.
+    pub sections: Vec<Section>,
+}
+
+impl Puzzle {
+    #[must_use]
+    pub fn parse(markdown: &str) -> Self {
+        let mut puzzle = Puzzle {
+            blocks: vec![],
+            sections: vec![Section::default()],
+        };
+
+        let mut lines = markdown.lines();
+
+        while let Some(line) = lines.next() {
+            let trimmed = line.trim_start();
+
+            if trimmed.starts_with('#') && trimmed.contains("Part Two") {
+                puzzle.sections.push(Section::default());
+                continue;
+            }
+
+            if trimmed.starts_with("```") {
+                let mut block = vec![];
+                for line in lines.by_ref() {
+                    if line.trim_start().starts_with("```") {
+                        break;
+                    }
+                    block.push(line);
+                }
+                puzzle.push_block(block.join("\n"));
+                continue;
+            }
+
+            if let Some(start) = trimmed.find("<pre>") {
+                let mut html_block = trimmed[start..].to_string();
+                while !html_block.contains("</pre>") {
+                    let Some(line) = lines.next() else {
+                        break;
+                    };
+                    html_block.push('\n');
+                    html_block.push_str(line);
+                }
+                let nodes = html::parse(&html_block);
+                if let Some(pre) = html::find_all(&nodes, "pre").first() {
+                    puzzle.push_block(pre.text().trim_end_matches('\n').to_string());
+                }
+                continue;
+            }
+
+            let section = puzzle.sections.last_mut().unwrap();
+            section.answers.extend(emphasised_code(line));
+        }
+
+        puzzle
+    }
+
+    fn push_block(&mut self, block: String) {
+        self.blocks.push(block);
+        let index = self.blocks.len() - 1;
+        self.sections.last_mut().unwrap().blocks.push(index);
+    }
+
+    /// The block most likely holding the example of a part: the first block of its section.
+    /// Falls back to the example of part one, as part two often reuses it.
+    #[must_use]
+    pub fn default_block(&self, part: u8) -> Option<usize> {
+        let section = self.sections.get(usize::from(part) - 1)?;
+        section
+            .blocks
+            .first()
+            .copied()
+            .or_else(|| self.default_block(1).filter(|_| part == 2))
+    }
+
+    /// The likely example answer of a part: the last emphasised code span of its section.
+    #[must_use]
+    pub fn default_answer(&self, part: u8) -> Option<&str> {
+        self.sections
+            .get(usize::from(part) - 1)?
+            .answers
+            .last()
+            .map(String::as_str)
+    }
+}
+
+/// The description of one part, `None` if the part is not unlocked yet.
+/// Part one ends where the heading of part two starts.
+#[must_use]
+pub fn part_text(markdown: &str, part: u8) -> Option<&str> {
+    let mut part_two = None;
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') && trimmed.contains("Part Two") {
+            part_two = Some(offset);
+            break;
+        }
+        offset += line.len();
+    }
+
+    match part {
+        1 => Some(&markdown[..part_two.unwrap_or(markdown.len())]),
+        2 => part_two.map(|start| &markdown[start..]),
+        _ => None,
+    }
+}
+
+/// Finds emphasised code spans in a line of markdown.
+fn emphasised_code(line: &str) -> Vec<String> {
+    let mut found = vec![];
+
+    for (open, close) in [
+        ("**`", "`**"),
+        ("*`", "`*"),
+        ("`*", "*`"),
+        ("<code><em>", "</em></code>"),
+    ] {
+        let mut rest = line;
+        while let Some(start) = rest.find(open) {
+            let after = &rest[start + open.len()..];
+            let Some(end) = after.find(close) else {
+                break;
+            };
+            let value = html::decode_entities(&after[..end]);
+            if value.is_empty() || value.contains('`') || value.contains('*') {
+                // not a span, the delimiters belong to different spans.
+                rest = &rest[start + 1..];
+                continue;
+            }
+            found.push((line.len() - rest.len() + start, value));
+            rest = &after[end + close.len()..];
+        }
+    }
+
+    // patterns overlap, e.g. `**` contains `*`. keep one match per position, in order.
+    found.sort_by_key(|(pos, _)| *pos);
+    found.dedup_by(|a, b| a.1 == b.1 && a.0 <= b.0 + 2);
+    found.into_iter().map(|(_, value)| value).collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasised_code, part_text, Puzzle};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the values are `12` and `38`. Adding these together produces *`50`*.
+
+Your puzzle answer was `54304`.
+
+## --- Part Two ---
+
+For example:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`281`*.
+";
+
+    #[test]
+    fn finds_blocks_and_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(
+            puzzle.blocks,
+            vec!["1abc2\npqr3stu8vwx", "two1nine\neightwothree"]
+        );
+        assert_eq!(puzzle.sections.len(), 2);
+        assert_eq!(puzzle.sections[0].blocks, vec![0]);
+        assert_eq!(puzzle.sections[0].answers, vec!["50"]);
+
+        assert_eq!(puzzle.default_block(1), Some(0));
+        assert_eq!(puzzle.default_block(2), Some(1));
+        assert_eq!(puzzle.default_answer(1), Some("50"));
+        assert_eq!(puzzle.default_answer(2), Some("281"));
+    }
+
+    #[test]
+    fn reuses_first_block_for_part_two() {
+        let puzzle = Puzzle::parse("```\na\n```\n*`1`*\n## --- Part Two ---\nnow *`2`*");
+        assert_eq!(puzzle.default_block(2), Some(0));
+        assert_eq!(puzzle.default_answer(2), Some("2"));
+    }
+
+    #[test]
+    fn handles_html_blocks() {
+        let puzzle = Puzzle::parse(
+            "<pre><code>a &lt; b\nc\n</code></pre>\n<p>The answer is <code><em>7</em></code>.</p>",
+        );
+        assert_eq!(puzzle.blocks, vec!["a < b\nc"]);
+        assert_eq!(puzzle.default_answer(1), Some("7"));
+        assert_eq!(puzzle.default_answer(2), None);
+    }
+
+    #[test]
+    fn finds_emphasised_code() {
+        assert_eq!(
+            emphasised_code("a `1` b *`2`* c **`3`** d `*4*` e <code><em>5</em></code>"),
+            vec!["2", "3", "4", "5"]
+        );
+    }
+
+    #[test]
+    fn splits_parts() {
+        let part_one = part_text(PUZZLE, 1).unwrap();
+        assert!(part_one.starts_with("## --- Day 1"));
+        assert!(part_one
+            .trim_end()
+            .ends_with("Your puzzle answer was `54304`."));
+        assert!(part_text(PUZZLE, 2)
+            .unwrap()
+            .starts_with("## --- Part Two ---"));
+
+        assert_eq!(part_text(part_one, 1), Some(part_one));
+        assert_eq!(part_text(part_one, 2), None);
+    }
+}
@@ -1,11 +1,32 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Each year can have its own table, delimited by `<!--- benchmarking table 2023 --->` markers.
+/// If the readme has no markers for a year, the shared `<!--- benchmarking table --->` markers are used.
+///
+/// Optional columns are selected with `columns` in `[readme]` of `aoc.toml`, or `AOC_README_COLUMNS`, a
+/// comma-separated list of [`Column`]s. If `chart` or `AOC_README_CHART` is set, a bar chart of the runtime per day is written to that path
+/// (`{year}` is replaced with the year) and shown below the table.
+use std::fmt::{Display, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::{env, fs, io};
 
-use crate::Day;
+use crate::template::config::{self, Config};
+use crate::template::get_path_for_bin;
+use crate::template::report::Status;
+use crate::template::stats::Stats;
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+pub const COLUMNS_ENV: &str = "AOC_README_COLUMNS";
+pub const CHART_ENV: &str = "AOC_README_CHART";
+
+fn year_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -18,26 +39,113 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Time spent parsing the input, if the day parses it once for both parts.
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
+    /// Whether the answer of each part matches the known answer, if one is recorded.
+    pub verified: [Option<bool>; 2],
+    /// Why a part has no timing, if it was skipped, timed out or killed.
+    pub unfinished: [Option<Status>; 2],
     pub total_nanos: f64,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+/// Optional information shown in the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Number of samples each part was benchmarked with.
+    Samples,
+    /// Minimum, 95th percentile and standard deviation below the median of each part.
+    Spread,
+    /// Whether the answers match the known answers.
+    Verified,
+    /// Share of the day in the total runtime.
+    Share,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "samples" => Ok(Column::Samples),
+            "spread" => Ok(Column::Spread),
+            "verified" => Ok(Column::Verified),
+            "share" => Ok(Column::Share),
+            x => Err(Error::Parser(format!(
+                "unknown column \"{x}\", expecting \"samples\", \"spread\", \"verified\" or \"share\""
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub columns: Vec<Column>,
+    /// Path the runtime chart is written to, relative to the readme.
+    pub chart: Option<String>,
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Spread],
+            chart: None,
+        }
+    }
+}
+
+impl Options {
+    /// Reads the options from `[readme]` of the config, overridden by `AOC_README_COLUMNS` and `AOC_README_CHART`.
+    pub fn from_config(year: Year, config: &Config) -> Result<Self, Error> {
+        let mut options = Options::default();
+
+        let columns = env::var(COLUMNS_ENV)
+            .ok()
+            .map(|columns| columns.split(',').map(str::to_string).collect())
+            .or_else(|| config.readme_columns.clone());
+
+        if let Some(columns) = columns {
+            options.columns = columns
+                .iter()
+                .filter(|c| !c.trim().is_empty())
+                .map(|c| c.parse())
+                .collect::<Result<_, _>>()?;
+        }
+
+        options.chart = env::var(CHART_ENV)
+            .ok()
+            .or_else(|| config.readme_chart.clone())
+            .filter(|path| !path.is_empty())
+            .map(|path| path.replace("{year}", &year.to_string()));
+
+        Ok(options)
+    }
+
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+pub(crate) struct TablePosition {
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
+}
+
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,73 +166,282 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &Options,
+) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+
+    // the parse column only appears once a day parses its input separately.
+    let has_parse = timings.iter().any(|t| t.parse.is_some());
+
+    let (mut head, mut align) = if has_parse {
+        (
+            "| Day | Parse | Part 1 | Part 2 |".to_string(),
+            "| :---: | :---: | :---: | :---:  |".to_string(),
+        )
+    } else {
+        (
+            "| Day | Part 1 | Part 2 |".to_string(),
+            "| :---: | :---: | :---:  |".to_string(),
+        )
+    };
+
+    for (column, title) in [
+        (Column::Samples, "Samples"),
+        (Column::Verified, "Verified"),
+        (Column::Share, "Share"),
+    ] {
+        if options.has(column) {
+            let _ = write!(head, " {title} |");
+            align.push_str(" :---: |");
+        }
+    }
+
+    let mut lines: Vec<String> = vec![marker.into(), header, String::new(), head, align];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let total_nanos: f64 = timings.iter().map(|t| t.total_nanos).sum();
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let path = get_path_for_bin(year, timing.day);
+        let parse = if has_parse {
+            let cell = format_cell(timing.parse.as_ref(), None, options.has(Column::Spread));
+            format!(" {cell} |")
+        } else {
+            String::new()
+        };
+
+        let mut line = format!(
+            "| [Day {}]({}) |{parse} {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_cell(
+                timing.part_1.as_ref(),
+                timing.unfinished[0],
+                options.has(Column::Spread)
+            ),
+            format_cell(
+                timing.part_2.as_ref(),
+                timing.unfinished[1],
+                options.has(Column::Spread)
+            )
+        );
+
+        if options.has(Column::Samples) {
+            let samples = |stats: Option<&Stats>| match stats {
+                Some(s) if s.outliers == 1 => format!("{} (1 outlier)", s.samples),
+                Some(s) if s.outliers > 1 => format!("{} ({} outliers)", s.samples, s.outliers),
+                Some(s) => s.samples.to_string(),
+                None => "-".into(),
+            };
+            let _ = write!(
+                line,
+                " {} · {} |",
+                samples(timing.part_1.as_ref()),
+                samples(timing.part_2.as_ref())
+            );
+        }
+
+        if options.has(Column::Verified) {
+            let verified = |v: Option<bool>| match v {
+                Some(true) => "✅",
+                Some(false) => "❌",
+                None => "-",
+            };
+            let _ = write!(
+                line,
+                " {} {} |",
+                verified(timing.verified[0]),
+                verified(timing.verified[1])
+            );
+        }
+
+        if options.has(Column::Share) {
+            let share = if total_nanos > 0.0 {
+                timing.total_nanos / total_nanos * 100.0
+            } else {
+                0.0
+            };
+            let _ = write!(line, " {share:.1}% |");
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if let Some(chart) = &options.chart {
+        lines.push(String::new());
+        lines.push(format!("[![{year} runtime per day]({chart})]({chart})"));
+    }
+
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn format_cell(stats: Option<&Stats>, unfinished: Option<Status>, show_spread: bool) -> String {
+    if let Some(label) = unfinished.and_then(Status::label) {
+        return label.into();
+    }
+
+    match stats {
+        Some(stats) if stats.samples > 1 && show_spread => format!(
+            "`{:.1?}` <sub>min {:.1?} · p95 {:.1?} · σ {:.1?}</sub>",
+            stats.median, stats.min, stats.p95, stats.stddev
+        ),
+        Some(stats) => format!("`{:.1?}`", stats.median),
+        None => "`-`".into(),
+    }
+}
+
+/// Renders a horizontal bar chart of the runtime per day. Bars use a logarithmic scale,
+/// as runtimes commonly range from microseconds to seconds.
+#[allow(clippy::cast_precision_loss)]
+fn render_chart(year: Year, timings: &[Timings]) -> String {
+    const WIDTH: f64 = 640.0;
+    const LABEL_WIDTH: f64 = 70.0;
+    const VALUE_WIDTH: f64 = 90.0;
+    const ROW_HEIGHT: f64 = 22.0;
+    const TOP: f64 = 36.0;
+
+    let bar_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let height = TOP + ROW_HEIGHT * timings.len() as f64 + 10.0;
+
+    // one nanosecond is the smallest representable runtime, it maps to an empty bar.
+    let max_log = timings
+        .iter()
+        .map(|t| t.total_nanos.max(1.0).log10())
+        .fold(1.0, f64::max);
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">
+<rect width="100%" height="100%" fill="#ffffff"/>
+<text x="{LABEL_WIDTH}" y="20" font-size="14" font-weight="bold" fill="#333333">{year} runtime per day (median, log scale)</text>
+"##
+    );
+
+    for (i, timing) in timings.iter().enumerate() {
+        let y = TOP + ROW_HEIGHT * i as f64;
+        let width = (timing.total_nanos.max(1.0).log10() / max_log * bar_width).max(1.0);
+        let duration = std::time::Duration::from_nanos(timing.total_nanos as u64);
+
+        let _ = writeln!(
+            svg,
+            r##"<text x="{}" y="{}" text-anchor="end" fill="#333333">Day {}</text>"##,
+            LABEL_WIDTH - 8.0,
+            y + 14.0,
+            timing.day.into_inner()
+        );
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{LABEL_WIDTH}" y="{y}" width="{width:.1}" height="{}" fill="#b7410e"/>"##,
+            ROW_HEIGHT - 6.0
+        );
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{}" fill="#333333">{duration:.1?}</text>"##,
+            LABEL_WIDTH + width + 6.0,
+            y + 14.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &Options,
+) -> Result<(), Error> {
+    let year_marker = year_marker(year);
+    let marker = if s.contains(&year_marker) {
+        year_marker.as_str()
+    } else {
+        MARKER
+    };
+
+    let positions = locate_table(s, marker)?;
+    let table = construct_table("##", marker, year, timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &Options,
+) -> Result<(), Error> {
+    let path = &config::get().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+
+    if let Some(chart) = &options.chart {
+        if let Some(dir) = Path::new(chart).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(chart, render_chart(year, &timings))?;
+    }
+
+    update_content(&mut readme, year, timings, total_millis, options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{render_chart, update_content, Column, Options, Timings, MARKER};
+    use crate::template::report::Status;
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn mock_stats(median_millis: u64) -> Option<Stats> {
+        Some(Stats {
+            samples: 10,
+            outliers: 0,
+            min: Duration::from_millis(median_millis - 1),
+            median: Duration::from_millis(median_millis),
+            p95: Duration::from_millis(median_millis + 2),
+            stddev: Duration::from_micros(500),
+        })
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                parse: None,
+                part_1: mock_stats(10),
+                part_2: mock_stats(20),
+                verified: [Some(true), Some(true)],
+                unfinished: [None, None],
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                parse: None,
+                part_1: mock_stats(30),
+                part_2: mock_stats(40),
+                verified: [Some(true), Some(false)],
+                unfinished: [None, None],
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                parse: None,
+                part_1: mock_stats(40),
+                part_2: Some(Stats::single(Duration::from_millis(50))),
+                verified: [None, None],
+                unfinished: [None, None],
                 total_nanos: 9e+10,
             },
         ]
@@ -134,47 +451,119 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn updates_year_specific_benchmarks() {
+        let mut s = format!(
+            "{}{}\n<!--- benchmarking table 2023 ---><!--- benchmarking table 2023 --->",
+            MARKER, MARKER
+        );
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(s.starts_with(&format!("{}{}\n", MARKER, MARKER)), true);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
+        assert_eq!(
+            s.matches("<!--- benchmarking table 2023 --->")
+                .collect::<Vec<&str>>()
+                .len(),
+            2
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            &Options::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## 2023 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` <sub>min 9.0ms · p95 12.0ms · σ 500.0µs</sub> | `20.0ms` <sub>min 19.0ms · p95 22.0ms · σ 500.0µs</sub> |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms` <sub>min 29.0ms · p95 32.0ms · σ 500.0µs</sub> | `40.0ms` <sub>min 39.0ms · p95 42.0ms · σ 500.0µs</sub> |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms` <sub>min 39.0ms · p95 42.0ms · σ 500.0µs</sub> | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +572,77 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_optional_columns() {
+        let options = Options {
+            columns: vec![Column::Samples, Column::Verified, Column::Share],
+            chart: Some("./.assets/benchmarks-2023.svg".into()),
+        };
+        let mut timings = get_mock_timings();
+        timings[1].part_2.as_mut().unwrap().outliers = 2;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 190.0, &options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## 2023 Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Samples | Verified | Share |",
+            "| :---: | :---: | :---:  | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `20.0ms` | 10 · 10 | ✅ ✅ | 15.8% |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms` | `40.0ms` | 10 · 10 (2 outliers) | ✅ ❌ | 36.8% |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms` | `50.0ms` | 10 · 1 | - - | 47.4% |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "[![2023 runtime per day](./.assets/benchmarks-2023.svg)](./.assets/benchmarks-2023.svg)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_unfinished_parts() {
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        timings[0].part_2 = None;
+        timings[0].unfinished = [None, Some(Status::TimedOut)];
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 10.0, &Options::default()).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` <sub>min 9.0ms · p95 12.0ms · σ 500.0µs</sub> | timed out |"
+        ));
+    }
+
+    #[test]
+    fn format_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.truncate(2);
+        timings[0].parse = Some(Stats::single(Duration::from_micros(5)));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 100.0, &Options::default()).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `5.0µs` | `10.0ms` <sub>"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `-` | `30.0ms` <sub>"));
+    }
+
+    #[test]
+    fn renders_chart() {
+        let svg = render_chart(year!(2023), &get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect x=").count(), 3);
+        assert!(svg.contains(">Day 4</text>"));
+        assert!(svg.contains(">90.0s</text>"));
+    }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!("share".parse::<Column>().unwrap(), Column::Share);
+        assert!("median".parse::<Column>().is_err());
+    }
 }
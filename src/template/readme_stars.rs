@@ -0,0 +1,228 @@
+/// Module that updates the readme with the stars collected per day, from local data only.
+///
+/// A part counts as solved if its answer is recorded in the answer store or the submission ledger
+/// holds a correct verdict for it. The table is delimited by `<!--- advent_readme_stars table --->`
+/// markers, which keeps it compatible with readmes set up for `advent-readme-stars`. Stars the table
+/// already shows are kept, local data cannot tell that a part was not solved.
+use std::fs;
+
+use crate::template::answers;
+use crate::template::config;
+use crate::template::ledger::{self, Verdict};
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::{all_days, Day, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Stars of one day, one per part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stars {
+    pub day: Day,
+    pub parts: [bool; 2],
+}
+
+/// Collects the stars of every day with at least one solved part.
+pub fn collect(year: Year) -> Result<Vec<Stars>, Error> {
+    let mut stars = vec![];
+
+    for day in all_days() {
+        let answers = answers::read(year, day).map_err(|e| Error::Parser(e.to_string()))?;
+        let ledger = ledger::read(year, day).map_err(|e| Error::Parser(e.to_string()))?;
+
+        let is_solved = |part: u8| {
+            answers.get(part).is_some()
+                || ledger
+                    .submissions
+                    .iter()
+                    .any(|s| s.part == part && s.verdict == Verdict::Correct)
+        };
+
+        let parts = [is_solved(1), is_solved(2)];
+        if parts.iter().any(|solved| *solved) {
+            stars.push(Stars { day, parts });
+        }
+    }
+
+    Ok(stars)
+}
+
+fn construct_table(year: Year, stars: &[Stars]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |solved: bool| if solved { "⭐" } else { " " };
+
+    for day in stars {
+        let n = day.day.into_inner();
+        lines.push(format!(
+            "| [Day {n}](https://adventofcode.com/{year}/day/{n}) | {} | {} |",
+            star(day.parts[0]),
+            star(day.parts[1])
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+/// Stars the table shows for `year`, e.g. put there by the `advent-readme-stars` workflow.
+fn existing_stars(table: &str, year: Year) -> Vec<Stars> {
+    if !table.contains(&format!("## {year} Results")) {
+        return vec![];
+    }
+
+    table
+        .lines()
+        .filter_map(|line| {
+            // `| [Day 1](...) | ⭐ | ⭐ |` splits into `["", "[Day 1](...)", "⭐", "⭐", ""]`.
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let ["", day, one, two, ""] = cells[..] else {
+                return None;
+            };
+            let day = day.strip_prefix("[Day ")?.split_once(']')?.0;
+            Some(Stars {
+                day: Day::new(day.parse().ok()?)?,
+                parts: [one.contains('⭐'), two.contains('⭐')],
+            })
+        })
+        .collect()
+}
+
+/// Adds the stars of `local` to those of `existing`, ordered by day.
+fn merge(existing: Vec<Stars>, local: Vec<Stars>) -> Vec<Stars> {
+    let mut stars = existing;
+
+    for day in local {
+        match stars.iter_mut().find(|s| s.day == day.day) {
+            Some(s) => {
+                s.parts[0] |= day.parts[0];
+                s.parts[1] |= day.parts[1];
+            }
+            None => stars.push(day),
+        }
+    }
+
+    stars.sort_by_key(|s| s.day);
+    stars
+}
+
+/// Rewrites the table with the stars it shows and `stars`. Returns the stars of the new table.
+fn update_content(s: &mut String, year: Year, stars: Vec<Stars>) -> Result<Vec<Stars>, Error> {
+    let positions = locate_table(s, MARKER)?;
+    let existing = existing_stars(&s[positions.pos_start..positions.pos_end], year);
+    let stars = merge(existing, stars);
+
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &construct_table(year, &stars),
+    );
+    Ok(stars)
+}
+
+/// Rewrites the stars table of the readme. Returns the number of stars.
+pub fn update(year: Year) -> Result<usize, Error> {
+    let path = &config::get().readme;
+    let stars = collect(year)?;
+
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let stars = update_content(&mut readme, year, stars)?;
+    fs::write(path, &readme)?;
+
+    Ok(stars
+        .iter()
+        .flat_map(|s| s.parts)
+        .filter(|solved| *solved)
+        .count())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Stars, MARKER};
+    use crate::{day, year};
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbar");
+        let stars = vec![
+            Stars {
+                day: day!(1),
+                parts: [true, true],
+            },
+            Stars {
+                day: day!(3),
+                parts: [true, false],
+            },
+        ];
+        update_content(&mut s, year!(2023), stars).unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, year!(2023), vec![]).unwrap();
+    }
+
+    #[test]
+    fn keeps_existing_stars() {
+        let table = [
+            MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ⭐ |",
+            "| [Day 5](https://adventofcode.com/2023/day/5) | ⭐ |   |",
+            MARKER,
+        ]
+        .join("\n");
+        let local = vec![
+            Stars {
+                day: day!(1),
+                parts: [true, false],
+            },
+            Stars {
+                day: day!(5),
+                parts: [false, true],
+            },
+        ];
+
+        let mut s = table.clone();
+        let stars = update_content(&mut s, year!(2023), local.clone()).unwrap();
+        assert_eq!(
+            stars.iter().map(|s| (s.day, s.parts)).collect::<Vec<_>>(),
+            [
+                (day!(1), [true, false]),
+                (day!(2), [true, true]),
+                (day!(5), [true, true])
+            ]
+        );
+
+        // the stars of another year are replaced.
+        let mut s = table.replace("2023", "2022");
+        assert_eq!(
+            update_content(&mut s, year!(2023), local.clone()).unwrap(),
+            local
+        );
+    }
+}
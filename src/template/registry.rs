@@ -0,0 +1,35 @@
+/// Registry of solutions that are linked into the main binary.
+///
+/// With the `registry` feature enabled, the build script compiles every `src/bin/{year}-{day}.rs` into the
+/// main binary as a module and registers the [`Solution`] entries that the `solution!` macro
+/// generates. Commands then call solutions in-process instead of spawning `cargo run` for every day.
+use std::sync::OnceLock;
+
+use crate::template::report::PartReport;
+use crate::{Day, Year};
+
+static SOLUTIONS: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// A solution that can be called in-process.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Runs both parts against an input, printing results the same way the solution binary does.
+    pub run: fn(&str) -> Vec<PartReport>,
+}
+
+/// Registers the solutions linked into the binary. Only the first call has an effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Looks up the linked solution for a day.
+/// Returns [`None`] if the day is not linked or the binary was built without the `registry` feature.
+#[must_use]
+pub fn find(year: Year, day: Day) -> Option<&'static Solution> {
+    SOLUTIONS
+        .get()?
+        .iter()
+        .find(|s| s.year == year && s.day == day)
+}
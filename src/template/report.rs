@@ -0,0 +1,423 @@
+/// Machine-readable result records that solution binaries hand back to the `all` command.
+///
+/// When the environment variable [`REPORT_FILE_ENV`] is set, the runner appends one JSON object
+/// per executed part to that file, independently of whatever the solution prints to stdout.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::{Allocations, Memory};
+use crate::template::stats::Stats;
+use crate::{Day, Year};
+
+/// Name of the environment variable that selects the file records are appended to.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed report record: {e}"),
+            Error::IO(e) => write!(f, "could not access report file: {e}"),
+        }
+    }
+}
+
+/// Output format of commands that report results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            x => Err(format!(
+                "unknown format \"{x}\", expecting \"human\" or \"json\""
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part was not run, as another part was selected with `--part`.
+    Skipped,
+    /// The part ran past the timeout of the part or its day.
+    TimedOut,
+    /// The process of the part ended abnormally, e.g. by exceeding the memory ceiling.
+    Killed,
+    /// The part panicked.
+    Panicked,
+    /// The part returned an error, e.g. as its input is malformed.
+    Errored,
+    /// The solution did not compile.
+    BuildFailed,
+    /// The process of the part exited with an error before the part ran, e.g. as its input is missing.
+    Failed,
+}
+
+impl Status {
+    /// Short description of a part that did not produce a result, as shown in tables.
+    #[must_use]
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            Status::Solved | Status::Unsolved => None,
+            Status::Skipped => Some("skipped"),
+            Status::TimedOut => Some("timed out"),
+            Status::Killed => Some("killed"),
+            Status::Panicked => Some("panicked"),
+            Status::Errored => Some("error"),
+            Status::BuildFailed => Some("build failed"),
+            Status::Failed => Some("failed"),
+        }
+    }
+
+    /// Whether the part failed to run, as opposed to running without finding an answer or being left out.
+    #[must_use]
+    pub fn is_failure(self) -> bool {
+        !matches!(self, Status::Solved | Status::Unsolved | Status::Skipped)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Skipped => "skipped",
+            Status::TimedOut => "timed_out",
+            Status::Killed => "killed",
+            Status::Panicked => "panicked",
+            Status::Errored => "errored",
+            Status::BuildFailed => "build_failed",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "skipped" => Ok(Status::Skipped),
+            "timed_out" => Ok(Status::TimedOut),
+            "killed" => Ok(Status::Killed),
+            "panicked" => Ok(Status::Panicked),
+            "errored" => Ok(Status::Errored),
+            "build_failed" => Ok(Status::BuildFailed),
+            "failed" => Ok(Status::Failed),
+            x => Err(Error::Parser(format!("unknown status \"{x}\""))),
+        }
+    }
+}
+
+/// The outcome of executing one part of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Why the part failed, e.g. the panic message and its location.
+    pub error: Option<String>,
+    pub stats: Stats,
+    /// Memory figures of the first, unbenched run of the part.
+    pub memory: Memory,
+}
+
+impl PartReport {
+    /// Report of a part that did not produce a result.
+    #[must_use]
+    pub fn unfinished(
+        year: Year,
+        day: Day,
+        part: u8,
+        status: Status,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            status,
+            answer: None,
+            error,
+            stats: Stats::default(),
+            memory: Memory::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        let mut obj: HashMap<String, JsonValue> = HashMap::new();
+        obj.insert("year".into(), f64::from(self.year.into_inner()).into());
+        obj.insert("day".into(), f64::from(self.day.into_inner()).into());
+        obj.insert("part".into(), f64::from(self.part).into());
+        obj.insert("status".into(), self.status.as_str().to_string().into());
+        obj.insert(
+            "answer".into(),
+            self.answer.clone().map_or(JsonValue::Null, JsonValue::from),
+        );
+        if let Some(error) = &self.error {
+            obj.insert("error".into(), error.clone().into());
+        }
+        obj.insert("samples".into(), (self.stats.samples as f64).into());
+        obj.insert("outliers".into(), (self.stats.outliers as f64).into());
+        obj.insert("min_ns".into(), nanos(self.stats.min).into());
+        obj.insert("median_ns".into(), nanos(self.stats.median).into());
+        obj.insert("p95_ns".into(), nanos(self.stats.p95).into());
+        obj.insert("stddev_ns".into(), nanos(self.stats.stddev).into());
+        if let Some(allocations) = self.memory.allocations {
+            obj.insert("allocations".into(), float(allocations.count).into());
+            obj.insert("allocated_bytes".into(), float(allocations.bytes).into());
+            obj.insert(
+                "peak_heap_bytes".into(),
+                float(allocations.peak_bytes).into(),
+            );
+        }
+        if let Some(peak_rss) = self.memory.peak_rss {
+            obj.insert("peak_rss_bytes".into(), float(peak_rss).into());
+        }
+        obj.into()
+    }
+
+    pub fn from_json(value: &JsonValue) -> Result<Self, Error> {
+        let obj: &HashMap<String, JsonValue> = value
+            .get()
+            .ok_or_else(|| Error::Parser("expected an object".into()))?;
+
+        let year = Year::new(number(obj, "year")? as u16)
+            .ok_or_else(|| Error::Parser("year out of range".into()))?;
+
+        let day = Day::new(number(obj, "day")? as u8)
+            .ok_or_else(|| Error::Parser("day out of range".into()))?;
+
+        let answer = match obj.get("answer") {
+            Some(JsonValue::String(s)) => Some(s.clone()),
+            Some(JsonValue::Null) | None => None,
+            Some(_) => return Err(Error::Parser("\"answer\" is not a string".into())),
+        };
+
+        let error = match obj.get("error") {
+            Some(JsonValue::String(s)) => Some(s.clone()),
+            Some(JsonValue::Null) | None => None,
+            Some(_) => return Err(Error::Parser("\"error\" is not a string".into())),
+        };
+
+        // allocations are only counted with the `alloc_profile` feature, older records lack all memory figures.
+        let allocations = if obj.contains_key("allocations") {
+            Some(Allocations {
+                count: number(obj, "allocations")?,
+                bytes: number(obj, "allocated_bytes")?,
+                peak_bytes: number(obj, "peak_heap_bytes")?,
+            })
+        } else {
+            None
+        };
+
+        let peak_rss = match obj.get("peak_rss_bytes") {
+            Some(_) => Some(number(obj, "peak_rss_bytes")?),
+            None => None,
+        };
+
+        let status: &String = obj
+            .get("status")
+            .and_then(JsonValue::get)
+            .ok_or_else(|| Error::Parser("missing field \"status\"".into()))?;
+
+        Ok(Self {
+            year,
+            day,
+            part: number(obj, "part")? as u8,
+            status: status.parse()?,
+            answer,
+            error,
+            stats: Stats {
+                samples: number(obj, "samples")? as usize,
+                outliers: number(obj, "outliers")? as usize,
+                min: duration(obj, "min_ns")?,
+                median: duration(obj, "median_ns")?,
+                p95: duration(obj, "p95_ns")?,
+                stddev: duration(obj, "stddev_ns")?,
+            },
+            memory: Memory {
+                allocations,
+                peak_rss,
+            },
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos(duration: Duration) -> f64 {
+    duration.as_nanos() as f64
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn float(x: u64) -> f64 {
+    x as f64
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn number(obj: &HashMap<String, JsonValue>, key: &str) -> Result<u64, Error> {
+    obj.get(key)
+        .and_then(JsonValue::get::<f64>)
+        .map(|x| *x as u64)
+        .ok_or_else(|| Error::Parser(format!("missing numeric field \"{key}\"")))
+}
+
+fn duration(obj: &HashMap<String, JsonValue>, key: &str) -> Result<Duration, Error> {
+    number(obj, key).map(Duration::from_nanos)
+}
+
+/// Appends `report` to the file selected by [`REPORT_FILE_ENV`], if any.
+pub fn emit(report: &PartReport) -> Result<(), Error> {
+    let Ok(path) = std::env::var(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = report
+        .to_json()
+        .stringify()
+        .map_err(|e| Error::Parser(e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Reads all records from a report file written by [`emit`].
+pub fn read(path: &Path) -> Result<Vec<PartReport>, Error> {
+    parse_lines(&fs::read_to_string(path)?)
+}
+
+fn parse_lines(s: &str) -> Result<Vec<PartReport>, Error> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let value: JsonValue = l.parse().map_err(|e| Error::Parser(format!("{e}")))?;
+            PartReport::from_json(&value)
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_lines, PartReport, Status};
+    use crate::template::alloc::{Allocations, Memory};
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn mock_report() -> PartReport {
+        PartReport {
+            year: year!(2023),
+            day: day!(5),
+            part: 2,
+            status: Status::Solved,
+            answer: Some("line 1\n\"line 2\"".into()),
+            error: None,
+            stats: Stats {
+                samples: 100,
+                outliers: 3,
+                min: Duration::from_nanos(900),
+                median: Duration::from_micros(1),
+                p95: Duration::from_nanos(1200),
+                stddev: Duration::from_nanos(50),
+            },
+            memory: Memory::default(),
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let report = mock_report();
+        let line = report.to_json().stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn roundtrip_unsolved() {
+        let report = PartReport {
+            status: Status::Unsolved,
+            answer: None,
+            ..mock_report()
+        };
+        let line = report.to_json().stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn roundtrip_memory() {
+        let report = PartReport {
+            memory: Memory {
+                allocations: Some(Allocations {
+                    count: 340,
+                    bytes: 1_250_000,
+                    peak_bytes: 800_000,
+                }),
+                peak_rss: Some(3_145_728),
+            },
+            ..mock_report()
+        };
+        let line = report.to_json().stringify().unwrap();
+        assert!(line.contains("\"peak_heap_bytes\":800000"));
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn roundtrip_panicked() {
+        let report = PartReport::unfinished(
+            year!(2023),
+            day!(5),
+            1,
+            Status::Panicked,
+            Some("attempt to subtract with overflow, src/bin/2023-05.rs:42:13".into()),
+        );
+        let line = report.to_json().stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn ignores_blank_lines() {
+        let line = mock_report().to_json().stringify().unwrap();
+        let s = format!("{line}\n\n{line}\n");
+        assert_eq!(parse_lines(&s).unwrap().len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_missing_fields() {
+        parse_lines(r#"{"day": 1, "part": 1}"#).unwrap();
+    }
+}
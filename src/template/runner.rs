@@ -1,63 +1,479 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::aoc_client::{AocClient, AocClientError, SubmissionOutcome};
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+use std::any::Any;
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use super::alloc::{self, Memory};
+use super::input::Source;
+use super::ledger::{self, Submission};
+use super::limits::{self, Limits, RESUME_PART_ENV, TIMEOUT_EXIT_CODE};
+use super::report::{self, PartReport, Status};
+use super::solution::Solution;
+use super::stats::Stats;
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+const WARMUP_BUDGET: Duration = Duration::from_millis(100);
+const MIN_WARMUP_ITERATIONS: u32 = 3;
+const MAX_WARMUP_ITERATIONS: u32 = 1000;
+
+const BENCH_BUDGET: Duration = Duration::from_secs(1);
+const MIN_BENCH_ITERATIONS: u128 = 10;
+const MAX_BENCH_ITERATIONS: u128 = 10000;
+
+/// Part that is currently running, `0` in between parts.
+static CURRENT_PART: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    /// Buffer that runner output is redirected to while inside [`capture`].
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+
+    /// Message and location of the last panic on the current thread, see [`install_panic_hook`].
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+macro_rules! out {
+    ($($arg:tt)*) => { write_output(&format!($($arg)*)) };
+}
+
+macro_rules! outln {
+    ($($arg:tt)*) => { write_output(&format!("{}\n", format!($($arg)*))) };
+}
+
+fn write_output(s: &str) {
+    CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(buf) => buf.push_str(s),
+        None => {
+            let mut stdout = stdout();
+            let _ = stdout.write_all(s.as_bytes());
+            let _ = stdout.flush();
+        }
+    });
+}
+
+/// Runs `func`, collecting everything the runner prints on the current thread instead of writing it to stdout.
+/// Output the solution prints by itself is not captured.
+pub fn capture<R>(func: impl FnOnce() -> R) -> (R, String) {
+    let previous = CAPTURED.with(|captured| captured.replace(Some(String::new())));
+    let result = func();
+    let output = CAPTURED.with(|captured| captured.replace(previous));
+    (result, output.unwrap_or_default())
+}
+
+/// Return types of solution parts: `Option<T>`, or `Result<Option<T>, E>` for parts that can fail,
+/// e.g. on malformed input. The runner prints the error of a failing part, see [`ParseError`](super::parse::ParseError).
+pub trait PartResult {
+    type Answer: Display;
+
+    /// # Errors
+    /// The description of the error the part failed with.
+    fn into_result(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<Option<T>, E> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) -> PartReport {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let skipped_report = PartReport::unfinished(year, day, part, Status::Skipped, None);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    // parts before the resumed part already ran in an earlier process.
+    if part < resume_part() {
+        return skipped_report;
+    }
+
+    if !is_selected(part) {
+        outln!("{part_str}: {ANSI_ITALIC}skipped{ANSI_RESET}");
+        return emit(skipped_report);
+    }
+
+    CURRENT_PART.store(part, Ordering::SeqCst);
+
+    let watchdog = Limits::from_env().part_timeout.map(|timeout| {
+        Watchdog::arm(timeout, move || {
+            time_out(year, day, timeout);
+        })
+    });
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(
+            |input| func(input).into_result(),
+            input,
+            |result| {
+                if let Ok(result) = result {
+                    print_result(result, &part_str, "");
+                }
+            },
+            watchdog,
+            is_timed(),
+        )
+    }));
+
+    CURRENT_PART.store(0, Ordering::SeqCst);
+
+    let (result, stats, memory) = match outcome {
+        Ok(outcome) => outcome,
+        Err(payload) => {
+            let error = take_panic(payload.as_ref());
+            out!("\r");
+            outln!("{part_str}: {ANSI_ITALIC}panicked{ANSI_RESET}");
+            return emit(PartReport::unfinished(
+                year,
+                day,
+                part,
+                Status::Panicked,
+                Some(error),
+            ));
+        }
+    };
+
+    let result = match result {
+        Ok(result) => result,
+        Err(error) => {
+            out!("\r");
+            outln!("{part_str}: {ANSI_ITALIC}error{ANSI_RESET}");
+            outln!("{error}");
+            return emit(PartReport {
+                error: Some(error),
+                stats,
+                memory,
+                ..PartReport::unfinished(year, day, part, Status::Errored, None)
+            });
+        }
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &format!("{}{}", format_stats(&stats), format_memory(&memory)),
+    );
+
+    let report = PartReport {
+        year,
+        day,
+        part,
+        status: if result.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        },
+        answer: result.as_ref().map(ToString::to_string),
+        error: None,
+        stats,
+        memory,
+    };
+
+    let report = emit(report);
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        match submit_result(result, year, day, part) {
+            Some(Ok(outcome)) => println!("{outcome}"),
+            Some(Err(e)) => {
+                eprintln!("Failed to submit result: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+
+    report
+}
+
+/// Runs a [`Solution`]: parses the input once and runs both parts against the parsed input.
+/// Parsing is timed on its own and reported like a part, as part `0`.
+pub fn run_solution<S: Solution>(input: &str, year: Year, day: Day) -> Vec<PartReport> {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(
+            |input| S::parse(input).map_err(|e| e.to_string()),
+            input,
+            |result| {
+                if result.is_ok() {
+                    out!("Parse: {ANSI_ITALIC}done{ANSI_RESET}");
+                }
+            },
+            None,
+            is_timed(),
+        )
+    }));
+
+    let (status, error) = match outcome {
+        Ok((Ok(parsed), stats, memory)) => {
+            out!("\r");
+            outln!(
+                "Parse: {ANSI_ITALIC}done{ANSI_RESET}{}{}",
+                format_stats(&stats),
+                format_memory(&memory)
+            );
+
+            let mut reports = vec![];
+
+            // a resumed day already reported its parse step in the earlier process.
+            if resume_part() == 1 {
+                reports.push(emit(PartReport {
+                    stats,
+                    memory,
+                    ..PartReport::unfinished(year, day, 0, Status::Solved, None)
+                }));
+            }
+
+            reports.push(run_part(S::part_one, &parsed, year, day, 1));
+            reports.push(run_part(S::part_two, &parsed, year, day, 2));
+            return reports;
+        }
+        Ok((Err(error), _, _)) => {
+            out!("\r");
+            outln!("Parse: {ANSI_ITALIC}error{ANSI_RESET}");
+            outln!("{error}");
+            (Status::Errored, error)
+        }
+        Err(payload) => {
+            let error = take_panic(payload.as_ref());
+            out!("\r");
+            outln!("Parse: {ANSI_ITALIC}panicked{ANSI_RESET}");
+            (Status::Panicked, error)
+        }
+    };
+
+    // without parsed input, neither part can run.
+    let mut reports = vec![emit(PartReport::unfinished(
+        year,
+        day,
+        0,
+        status,
+        Some(error),
+    ))];
+
+    for part in (resume_part()..=2).filter(|part| is_selected(*part)) {
+        reports.push(emit(PartReport::unfinished(
+            year,
+            day,
+            part,
+            status,
+            Some("the input could not be parsed".into()),
+        )));
+    }
+
+    reports
+}
+
+/// Reads the input selected by the arguments of this process. If it cannot be read, the selected parts
+/// are reported as failed with the reason, so that `all` can show it, and the process ends.
+#[must_use]
+pub fn read_input(year: Year, day: Day) -> String {
+    let source = Source::from_env();
+
+    source.read(year, day).unwrap_or_else(|e| {
+        let error = source.explain(day, &e);
+        eprintln!(
+            "Could not read input from {}: {error}",
+            source.describe(year, day)
+        );
+
+        for part in (resume_part()..=2).filter(|part| is_selected(*part)) {
+            emit(PartReport::unfinished(
+                year,
+                day,
+                part,
+                Status::Failed,
+                Some(format!("could not read the input: {error}")),
+            ));
+        }
+
+        process::exit(1);
+    })
+}
+
+/// Enforces the [limits](crate::template::limits) handed to this process by `all`.
+/// Called by the solution binary before it reads its input.
+pub fn enforce_limits(year: Year, day: Day) {
+    let limits = Limits::from_env();
+
+    if let Some(memory_mb) = limits.memory_mb {
+        limits::limit_memory(memory_mb);
+    }
+
+    if let Some(timeout) = limits.day_timeout {
+        thread::spawn(move || {
+            thread::sleep(timeout);
+            time_out(year, day, timeout);
+        });
+    }
+}
+
+/// Calls `on_timeout` if it is not disarmed, by dropping it, within a timeout.
+struct Watchdog {
+    // dropping the sender disarms the watchdog.
+    _done: mpsc::Sender<()>,
+}
+
+impl Watchdog {
+    fn arm(timeout: Duration, on_timeout: impl FnOnce() + Send + 'static) -> Self {
+        let (done, finished) = mpsc::channel::<()>();
+        thread::spawn(move || {
+            if finished.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                on_timeout();
+            }
+        });
+        Self { _done: done }
+    }
+}
+
+/// Records the running part as timed out and ends the process.
+fn time_out(year: Year, day: Day, timeout: Duration) -> ! {
+    let part = CURRENT_PART.load(Ordering::SeqCst);
+
+    if part != 0 {
+        out!("\r");
+        outln!("Part {part}: {ANSI_ITALIC}timed out after {timeout:.1?}{ANSI_RESET}");
+        emit(PartReport::unfinished(
+            year,
+            day,
+            part,
+            Status::TimedOut,
+            Some(format!("ran longer than {timeout:.1?}")),
+        ));
+    }
+
+    process::exit(TIMEOUT_EXIT_CODE);
+}
+
+/// Records the message and location of panics, so a panicking part can be reported with them.
+/// Panics are still printed as usual.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        let message = panic_message(info.payload());
+        let error = match info.location() {
+            Some(location) => format!("{message}, {location}"),
+            None => message,
+        };
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(error));
+        previous(info);
+    }));
+}
+
+/// Message and location of the panic that unwound with `payload`.
+fn take_panic(payload: &(dyn Any + Send)) -> String {
+    LAST_PANIC
+        .with(|last| last.borrow_mut().take())
+        .unwrap_or_else(|| panic_message(payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/// Ends the process with a non-zero exit code if any part failed.
+pub fn exit_on_failure(reports: &[PartReport]) {
+    if reports.iter().any(|report| report.status.is_failure()) {
+        process::exit(1);
+    }
+}
+
+fn resume_part() -> u8 {
+    env::var(RESUME_PART_ENV)
+        .ok()
+        .and_then(|part| part.parse().ok())
+        .unwrap_or(1)
+}
+
+fn emit(report: PartReport) -> PartReport {
+    if let Err(e) = report::emit(&report) {
+        eprintln!("Failed to write result record: {e}");
+    }
+    report
+}
+
+/// Whether the `--time` argument asks to bench the parts.
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+/// Whether a part is selected by the `--part` argument passed to `solve` or `all`.
+/// Without the argument, both parts are run.
+fn is_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|x| x == "--part") {
+        Some(i) => args.get(i + 1).and_then(|p| p.parse::<u8>().ok()) == Some(part),
+        None => true,
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (a warmup phase, then approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Memory is measured on the first execution only, later runs may profit from warm caches.
+/// The `watchdog` of a part timeout only covers the first execution, benching takes its own time.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    watchdog: Option<Watchdog>,
+    time: bool,
+) -> (T, Stats, Memory) {
+    let ((result, base_time), memory) = alloc::measure(|| {
+        let timer = Instant::now();
+        let result = func(input.clone());
+        (result, timer.elapsed())
+    });
 
+    drop(watchdog);
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if time {
+        bench(func, input, base_time)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: Duration) -> Stats {
+    out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let estimate = warmup(&func, &input, base_time);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = (BENCH_BUDGET.as_nanos() / cmp::max(estimate.as_nanos(), 10))
+        .clamp(MIN_BENCH_ITERATIONS, MAX_BENCH_ITERATIONS);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,26 +485,67 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers)
+}
+
+/// Run the function without recording samples until caches and branch predictors have settled.
+/// Returns the mean duration of the warmup runs, which is a better estimate than the cold first run.
+fn warmup<I: Clone, T>(func: impl Fn(I) -> T, input: &I, base_time: Duration) -> Duration {
+    let timer = Instant::now();
+    let mut iterations: u32 = 0;
+
+    while iterations < MAX_WARMUP_ITERATIONS
+        && (iterations < MIN_WARMUP_ITERATIONS || timer.elapsed() < WARMUP_BUDGET)
+    {
+        func(input.clone());
+        iterations += 1;
+
+        // very slow solutions are not worth warming up for more than one run.
+        if base_time > WARMUP_BUDGET {
+            break;
+        }
+    }
+
+    timer.elapsed() / iterations
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_stats(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
+    } else {
+        let outliers = match stats.outliers {
+            0 => String::new(),
+            1 => ", 1 outlier".into(),
+            n => format!(", {n} outliers"),
+        };
+        format!(
+            " (median {:.1?}, min {:.1?}, p95 {:.1?}, σ {:.1?} @ {} samples{outliers})",
+            stats.median, stats.min, stats.p95, stats.stddev, stats.samples
+        )
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_memory(memory: &Memory) -> String {
+    let mut figures: Vec<String> = vec![];
+
+    if let Some(allocations) = memory.allocations {
+        figures.push(format!(
+            "{} allocs, {}, peak heap {}",
+            allocations.count,
+            alloc::format_bytes(allocations.bytes),
+            alloc::format_bytes(allocations.peak_bytes)
+        ));
+    }
+
+    // the resident set size is only worth showing next to the heap figures of an opted-in profile.
+    if let (Some(peak_rss), true) = (memory.peak_rss, memory.allocations.is_some()) {
+        figures.push(format!("peak rss {}", alloc::format_bytes(peak_rss)));
+    }
+
+    if figures.is_empty() {
+        String::new()
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" [{}]", figures.join(", "))
     }
 }
 
@@ -100,28 +557,28 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    out!("\r");
+                    outln!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{part}: ✖             ");
             }
         }
     }
@@ -129,12 +586,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the part matches the one passed to `--submit`.
+///  3. the submission ledger does not rule the answer out.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +616,80 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let source = Source::from_env();
+    if !source.is_puzzle() {
+        eprintln!(
+            "Submission blocked: the answer was computed from the {source}, not the puzzle input."
+        );
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+
+    let ledger = ledger::read(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read submission ledger: {e}");
+        process::exit(1);
+    });
+
+    let submitted_at = ledger::now();
+
+    if let Err(reason) = ledger.check(part, &answer, submitted_at) {
+        eprintln!("Submission blocked: {reason}");
+        process::exit(1);
+    }
+
+    println!("Submitting result...");
+    let outcome = AocClient::from_env().and_then(|client| client.submit(year, day, part, &answer));
+
+    if let Ok(outcome) = &outcome {
+        let submission = Submission::new(part, &answer, outcome, submitted_at);
+        if let Err(e) = ledger::append(year, day, &submission) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
+
+    Some(outcome)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{run_timed, Watchdog, BENCH_BUDGET};
+
+    #[test]
+    fn part_timeout_does_not_cover_benching() {
+        let timeout = Duration::from_millis(100);
+        assert!(timeout < BENCH_BUDGET);
+
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&timed_out);
+        let watchdog = Watchdog::arm(timeout, move || flag.store(true, Ordering::SeqCst));
+
+        let (_, stats, _) = run_timed(
+            |()| thread::sleep(Duration::from_millis(2)),
+            (),
+            |()| {},
+            Some(watchdog),
+            true,
+        );
+
+        assert!(stats.samples >= 10);
+        assert!(!timed_out.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn watchdog_fires_after_timeout() {
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&timed_out);
+        let _watchdog = Watchdog::arm(Duration::from_millis(10), move || {
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        thread::sleep(Duration::from_millis(200));
+        assert!(timed_out.load(Ordering::SeqCst));
+    }
 }
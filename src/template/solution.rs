@@ -0,0 +1,28 @@
+/// Solutions that parse their input once for both parts.
+///
+/// Implement [`Solution`] on a type and pass it to the `solution!` macro, e.g. `solution!(2023, 5, Almanac)`.
+/// The runner then parses the input once, times parsing separately from the parts and hands the
+/// parsed input to both parts. Days that use free `part_one` and `part_two` functions keep working as before.
+use std::fmt::Display;
+
+use crate::template::runner::PartResult;
+
+pub trait Solution {
+    /// The parsed input. It may borrow from the input, e.g. to keep names as `&str`.
+    type Parsed<'a>;
+    /// The error of malformed input, e.g. [`ParseError`](crate::template::parse::ParseError).
+    /// Use [`std::convert::Infallible`] if parsing cannot fail.
+    type Error: Display;
+    /// Return type of part one, e.g. `Option<u32>`, see [`PartResult`].
+    type PartOne: PartResult;
+    /// Return type of part two.
+    type PartTwo: PartResult;
+
+    /// # Errors
+    /// Returns an error if the input is malformed.
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Self::Error>;
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Self::PartOne;
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Self::PartTwo;
+}
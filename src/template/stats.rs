@@ -0,0 +1,116 @@
+/// Summary statistics over a set of benchmark samples.
+use std::time::Duration;
+
+/// Tukey fence multiplier used to reject outliers.
+const OUTLIER_FENCE: f64 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Stats {
+    /// Number of samples that were taken, including rejected outliers.
+    pub samples: usize,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes statistics from raw samples.
+    /// Samples outside of the Tukey fences (1.5 × IQR below Q1 or above Q3) are rejected before
+    /// the figures are computed, so that e.g. a single context switch does not skew the result.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats without samples");
+
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= lower && *x <= upper)
+            .collect();
+
+        let mean = kept.iter().sum::<f64>() / kept.len() as f64;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / kept.len() as f64;
+
+        Self {
+            samples: samples.len(),
+            outliers: sorted.len() - kept.len(),
+            min: from_nanos(kept[0]),
+            median: from_nanos(percentile(&kept, 50.0)),
+            p95: from_nanos(percentile(&kept, 95.0)),
+            stddev: from_nanos(variance.sqrt()),
+        }
+    }
+
+    /// Creates statistics for a single, unbenched execution.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self::from_samples(&[duration])
+    }
+}
+
+/// Linear-interpolated percentile of an already sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0) * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - rank.floor())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::single(Duration::from_micros(5));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_micros(5));
+        assert_eq!(stats.median, Duration::from_micros(5));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn computes_figures() {
+        let stats = Stats::from_samples(&nanos(&[50, 10, 40, 20, 30]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.p95, Duration::from_nanos(48));
+        assert_eq!(stats.stddev, Duration::from_nanos(14));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.p95, Duration::from_nanos(102));
+    }
+}
@@ -0,0 +1,11 @@
+advent_of_code::solution!({{year}}, {{day}});
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    None
+}
+
+advent_of_code::example_tests!();
@@ -0,0 +1,46 @@
+advent_of_code::solution!({{year}}, {{day}});
+
+struct Grid {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    fn parse(input: &str) -> Self {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let width = lines.first().map_or(0, |line| line.len());
+        Self {
+            cells: lines.concat(),
+            width,
+            height: lines.len(),
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// Orthogonal neighbours of a cell that lie inside the grid.
+    fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx)?;
+                let y = y.checked_add_signed(dy)?;
+                self.get(x, y).map(|_| (x, y))
+            })
+    }
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let grid = Grid::parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let grid = Grid::parse(input);
+    None
+}
+
+advent_of_code::example_tests!();
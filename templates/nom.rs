@@ -0,0 +1,34 @@
+advent_of_code::solution!({{year}}, {{day}});
+
+use nom::{
+    character::complete::{digit1, line_ending, space1},
+    combinator::{all_consuming, map_res, opt},
+    multi::{separated_list0, separated_list1},
+    sequence::terminated,
+    IResult,
+};
+
+type Line = Vec<u64>;
+
+fn parse_line(input: &str) -> IResult<&str, Line> {
+    separated_list1(space1, map_res(digit1, str::parse))(input)
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Line>> {
+    all_consuming(terminated(
+        separated_list0(line_ending, parse_line),
+        opt(line_ending),
+    ))(input)
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let (_, input) = parse_input(input).unwrap();
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let (_, input) = parse_input(input).unwrap();
+    None
+}
+
+advent_of_code::example_tests!();
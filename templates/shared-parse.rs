@@ -0,0 +1,23 @@
+advent_of_code::solution!({{year}}, {{day}});
+
+struct Input {
+    lines: Vec<String>,
+}
+
+fn parse(input: &str) -> Input {
+    Input {
+        lines: input.lines().map(ToString::to_string).collect(),
+    }
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let input = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let input = parse(input);
+    None
+}
+
+advent_of_code::example_tests!();
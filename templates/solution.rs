@@ -0,0 +1,28 @@
+use std::convert::Infallible;
+
+use advent_of_code::template::solution::Solution;
+
+advent_of_code::solution!({{year}}, {{day}}, Puzzle);
+
+struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<&'a str>;
+    type Error = Infallible;
+    type PartOne = Option<{{answer_type}}>;
+    type PartTwo = Option<{{answer_type}}>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, Infallible> {
+        Ok(input.lines().collect())
+    }
+
+    fn part_one(lines: &Vec<&str>) -> Option<{{answer_type}}> {
+        None
+    }
+
+    fn part_two(lines: &Vec<&str>) -> Option<{{answer_type}}> {
+        None
+    }
+}
+
+advent_of_code::example_tests!(solution = Puzzle);